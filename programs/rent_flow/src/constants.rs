@@ -4,3 +4,147 @@ use anchor_lang::prelude::*;
 pub const SEED: &str = "anchor";
 // the code start from here
 // so start it form here
+
+/// Basis point denominator (10_000 bps = 100%).
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Time for a seized booking's Dutch auction to decay from full debt to the floor once bidding opens.
+pub const AUCTION_DECAY_PERIOD: i64 = 60 * 60 * 24 * 30;
+
+/// Lowest price (as bps of outstanding debt) a seized booking can be sold for.
+pub const AUCTION_FLOOR_BPS: u64 = 5_000;
+
+/// After this long unsold past the redemption deadline, anyone can write a defaulted obligation off (admin: any time).
+pub const WRITE_OFF_DELAY: i64 = AUCTION_DECAY_PERIOD * 2;
+
+/// Default bond required per unit of outstanding principal originated by an integrator (10%).
+pub const DEFAULT_MIN_BOND_BPS: u16 = 1_000;
+
+/// Default share of a defaulted obligation's debt slashed from the integrator bond (50%).
+pub const DEFAULT_SLASH_BPS: u16 = 5_000;
+
+/// Default delay before unbonded integrator collateral can be withdrawn.
+pub const DEFAULT_UNBONDING_PERIOD: i64 = 60 * 60 * 24 * 7;
+
+/// Settling up to this long after `end_date` still counts as on time.
+pub const ON_TIME_WINDOW: i64 = 60 * 60 * 24;
+
+// --- Host Reputation Tuning ---
+/// Extra LTV earned per clean (on-time or early) repayment.
+pub const REP_LTV_BONUS_BPS: u64 = 50;
+/// Cap on the reputation LTV bonus.
+pub const REP_MAX_LTV_BONUS_BPS: u64 = 1_000;
+/// LTV lost per late repayment.
+pub const REP_LATE_LTV_PENALTY_BPS: u64 = 250;
+/// LTV lost per historical default.
+pub const REP_DEFAULT_LTV_PENALTY_BPS: u64 = 1_500;
+/// Rate discount earned per clean repayment.
+pub const REP_RATE_DISCOUNT_BPS: u64 = 25;
+/// Cap on the reputation rate discount.
+pub const REP_MAX_RATE_DISCOUNT_BPS: u64 = 300;
+/// Rate surcharge per late repayment.
+pub const REP_LATE_RATE_SURCHARGE_BPS: u64 = 100;
+/// Rate surcharge per historical default.
+pub const REP_DEFAULT_RATE_SURCHARGE_BPS: u64 = 300;
+
+// --- Default Pool Rate Model ---
+pub const DEFAULT_BASE_RATE_BPS: u16 = 500;
+pub const DEFAULT_SLOPE1_BPS: u16 = 500;
+pub const DEFAULT_KINK_BPS: u16 = 8_000;
+pub const DEFAULT_SLOPE2_BPS: u16 = 3_000;
+
+// --- Pause Flags (ProtocolConfig.paused) ---
+pub const PAUSE_MINT: u8 = 1 << 0;
+pub const PAUSE_BORROW: u8 = 1 << 1;
+pub const PAUSE_SETTLE: u8 = 1 << 2;
+pub const PAUSE_LP_DEPOSIT: u8 = 1 << 3;
+pub const PAUSE_LP_WITHDRAW: u8 = 1 << 4;
+pub const PAUSE_LIQUIDATE: u8 = 1 << 5;
+pub const PAUSE_ALL: u8 = PAUSE_MINT | PAUSE_BORROW | PAUSE_SETTLE | PAUSE_LP_DEPOSIT | PAUSE_LP_WITHDRAW | PAUSE_LIQUIDATE;
+
+// --- Settlement Waterfall ---
+/// Penalty on principal when a host settles before the booking's end_date.
+pub const EARLY_EXIT_PENALTY_BPS: u64 = 500;
+/// Default protocol (treasury) fee, taken from interest + penalties.
+pub const DEFAULT_PROTOCOL_FEE_BPS: u16 = 1_000;
+/// Tip paid from the repayment escrow to whoever cranks a due settlement, on principal.
+pub const KEEPER_TIP_BPS: u64 = 10;
+/// Fixed-point scale of PoolVault.acc_yield_per_share and loss_index.
+pub const YIELD_PRECISION: u128 = 1_000_000_000_000;
+
+// --- Liquidation Bounty (USDC base units / bps of outstanding debt) ---
+pub const DEFAULT_BOUNTY_FIXED: u64 = 1_000_000;  // 1 USDC
+pub const DEFAULT_BOUNTY_BPS: u16 = 100;          // 1% of debt
+pub const DEFAULT_BOUNTY_CAP: u64 = 100_000_000;  // 100 USDC
+
+// --- Grace Periods (seconds after end_date before a loan can be liquidated) ---
+/// Pool-wide default; tier and integrator overrides take precedence when set.
+pub const DEFAULT_GRACE_PERIOD: i64 = 60 * 60 * 24 * 7;
+/// Upper bound for any configured grace period.
+pub const MAX_GRACE_PERIOD: i64 = 60 * 60 * 24 * 90;
+
+// --- Redemption (host buys back seized collateral before the auction) ---
+pub const DEFAULT_REDEMPTION_WINDOW: i64 = 60 * 60 * 24 * 3;
+pub const DEFAULT_REDEMPTION_PENALTY_BPS: u16 = 1_000; // 10% of principal
+/// Upper bound for the redemption window, so a seized booking cannot be frozen indefinitely.
+pub const MAX_REDEMPTION_WINDOW: i64 = 60 * 60 * 24 * 30;
+
+/// Default wait between queueing and executing a parameter change.
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 60 * 60 * 24 * 2;
+
+// --- Oracle Proofs ---
+/// Longest validity window (expires_at - issued_at) accepted on a BookingProof.
+pub const MAX_PROOF_VALIDITY: i64 = 60 * 60;
+/// Tolerated drift between the oracle's clock and the cluster clock.
+pub const MAX_CLOCK_SKEW: i64 = 60;
+/// Size of one NonceBitmap page; each byte tracks 8 nonces.
+pub const NONCE_PAGE_BYTES: usize = 512;
+pub const NONCES_PER_PAGE: u64 = NONCE_PAGE_BYTES as u64 * 8;
+pub const MAX_BOOKING_ID_LEN: usize = 32;
+
+// --- Properties ---
+pub const MAX_LISTING_ID_LEN: usize = 32;
+pub const MAX_REGION_LEN: usize = 16;
+
+// --- Batch Minting ---
+/// Most bookings one mint_bookings_batch call may mint (transaction size bounds it well before compute).
+pub const MAX_BATCH_BOOKINGS: usize = 8;
+/// remaining_accounts per booking: nft_mint, host_ata, booking_obligation, nonce_page, property.
+pub const BATCH_ACCOUNTS_PER_BOOKING: usize = 5;
+/// Compute one booking mint needs (mint + ATA + obligation + nonce); checked before each.
+pub const BOOKING_MINT_COMPUTE_UNITS: u64 = 60_000;
+
+// --- Merkle Booking Roots ---
+/// Size of a BookingBatch's claimed-leaf bitmap; each byte tracks 8 leaves.
+pub const ROOT_BITMAP_BYTES: usize = 512;
+pub const MAX_ROOT_LEAVES: u32 = ROOT_BITMAP_BYTES as u32 * 8;
+/// Longest a posted root stays mintable; also bounds its leaves' proof validity.
+pub const MAX_ROOT_VALIDITY: i64 = 60 * 60 * 24 * 7;
+
+// --- Price Feeds ---
+/// Upper bound on CurrencyFeed.max_staleness.
+pub const MAX_PRICE_STALENESS: i64 = 60 * 60;
+/// Upper bound on CurrencyFeed.max_confidence_bps.
+pub const MAX_PRICE_CONFIDENCE_BPS: u16 = 500;
+
+/// Maximum number of keys in the admin multisig (approvals are a u16 bitmap).
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// Maximum number of accounts an admin proposal can pin for execution.
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 12;
+
+// --- Account Layout Versions (bump when a layout changes, then ship a migrate_* path) ---
+pub const OBLIGATION_VERSION: u8 = 9;
+pub const POSITION_VERSION: u8 = 3;
+pub const POOL_VAULT_VERSION: u8 = 5;
+pub const SUPPORTED_TOKEN_VERSION: u8 = 3;
+pub const INTEGRATOR_VERSION: u8 = 1;
+// Created versioned: nothing to migrate (or gate on) until their layouts first change
+pub const INTEGRATOR_BOND_VERSION: u8 = 1;
+pub const HOST_PROFILE_VERSION: u8 = 1;
+pub const PROTOCOL_CONFIG_VERSION: u8 = 1;
+pub const RESERVE_FUND_VERSION: u8 = 1;
+pub const PROPERTY_VERSION: u8 = 1;
+
+/// Spare bytes kept at the end of versioned accounts so new fields fit without a realloc.
+pub const ACCOUNT_RESERVED_BYTES: usize = 64;
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    sysvar::instructions as instructions_sysvar,
+};
+use crate::error::ErrorCode;
+
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+/// Offsets header size and per-signature entry size of an Ed25519 program instruction.
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+
+/// One (signer, message) pair the Ed25519 program verified in the same transaction.
+pub struct SignedMessage<'a> {
+    pub signer: Pubkey,
+    pub message: &'a [u8],
+}
+
+/// Loads the instruction right before the current one and checks it is an Ed25519 verify.
+pub fn load_preceding_ed25519(ixs: &AccountInfo) -> Result<(Instruction, u16)> {
+    let current_index = instructions_sysvar::load_current_index_checked(ixs)?;
+    let prev_index = current_index.checked_sub(1).ok_or(ErrorCode::InvalidInstructionIndex)?;
+    let ix = instructions_sysvar::load_instruction_at_checked(prev_index as usize, ixs)?;
+
+    require_keys_eq!(ix.program_id, ED25519_PROGRAM_ID, ErrorCode::InvalidProgramId);
+    Ok((ix, prev_index))
+}
+
+/// Every signature carried by an Ed25519 instruction whose key and message live in
+/// that instruction's own data (the runtime has already checked the signatures).
+pub fn signed_messages(data: &[u8], ix_index: u16) -> Result<Vec<SignedMessage<'_>>> {
+    let count = *data.first().ok_or(ErrorCode::InvalidOffset)? as usize;
+    let read_u16 = |at: usize| -> Result<u16> {
+        let bytes = data.get(at..at + 2).ok_or(ErrorCode::InvalidOffset)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+    let local = |index: u16| index == u16::MAX || index == ix_index;
+
+    let mut messages = Vec::with_capacity(count);
+    for i in 0..count {
+        let at = HEADER_LEN + i * OFFSETS_LEN;
+        let signature_ix = read_u16(at + 2)?;
+        let pubkey_offset = read_u16(at + 4)? as usize;
+        let pubkey_ix = read_u16(at + 6)?;
+        let message_offset = read_u16(at + 8)? as usize;
+        let message_size = read_u16(at + 10)? as usize;
+        let message_ix = read_u16(at + 12)?;
+
+        // Data pulled from other instructions could be swapped without us seeing it
+        require!(
+            local(signature_ix) && local(pubkey_ix) && local(message_ix),
+            ErrorCode::InvalidInstructionIndex
+        );
+
+        let signer = data.get(pubkey_offset..pubkey_offset + 32).ok_or(ErrorCode::InvalidOffset)?;
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::InvalidOffset)?;
+        messages.push(SignedMessage {
+            signer: Pubkey::try_from(signer).map_err(|_| error!(ErrorCode::InvalidOffset))?,
+            message,
+        });
+    }
+    Ok(messages)
+}
+
+/// Requires the preceding Ed25519 instruction to carry `signer`'s signature over exactly `message`.
+pub fn verify_preceding_signature(ixs: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let (ix, ix_index) = load_preceding_ed25519(ixs)?;
+    let signed = signed_messages(&ix.data, ix_index)?;
+
+    let mut by_signer = signed.iter().filter(|signed| signed.signer == *signer).peekable();
+    require!(by_signer.peek().is_some(), ErrorCode::InvalidOracleKey);
+    require!(by_signer.any(|signed| signed.message == message), ErrorCode::OracleMessageMismatch);
+    Ok(())
+}
+
+/// Requires the preceding Ed25519 instruction to attest every message in `messages`:
+/// either one `signer` signature over `batch_message`, or one per message.
+pub fn verify_preceding_batch(
+    ixs: &AccountInfo,
+    signer: &Pubkey,
+    messages: &[Vec<u8>],
+    batch_message: &[u8],
+) -> Result<()> {
+    let (ix, ix_index) = load_preceding_ed25519(ixs)?;
+    let signed = signed_messages(&ix.data, ix_index)?;
+
+    let by_signer: Vec<&[u8]> = signed
+        .iter()
+        .filter(|signed| signed.signer == *signer)
+        .map(|signed| signed.message)
+        .collect();
+    require!(!by_signer.is_empty(), ErrorCode::InvalidOracleKey);
+    if by_signer.contains(&batch_message) {
+        return Ok(());
+    }
+    require!(
+        messages.iter().all(|message| by_signer.contains(&message.as_slice())),
+        ErrorCode::OracleMessageMismatch
+    );
+    Ok(())
+}
@@ -61,5 +61,224 @@ pub enum ErrorCode {
     #[msg("The grace period has not yet ended.")]
     GracePeriodNotOver,
 
-   
+    // --- Protocol Governance Errors ---
+    #[msg("The signer is not the protocol admin.")]
+    NotProtocolAdmin,
+
+    #[msg("Basis point values must not exceed 10,000.")]
+    InvalidBps,
+
+    // --- Default & Recovery Errors ---
+    #[msg("The obligation has not been seized by liquidation.")]
+    NotDefaulted,
+
+    #[msg("The current auction price exceeds the buyer's maximum.")]
+    AuctionPriceTooHigh,
+
+    // --- Integrator Bond Errors ---
+    #[msg("The integrator bond does not cover its outstanding originated principal.")]
+    InsufficientIntegratorBond,
+
+    #[msg("The unbonding delay has not yet elapsed.")]
+    UnbondingNotReady,
+
+    #[msg("The amount must be greater than zero.")]
+    InvalidAmount,
+
+    #[msg("The token mint does not match the expected currency.")]
+    MintMismatch,
+
+    // --- Host Reputation Errors ---
+    #[msg("The host has an uncured default and cannot borrow.")]
+    HostBlocked,
+
+    #[msg("The requested funding exceeds the booking's loan-to-value limit.")]
+    ExceedsLtv,
+
+    #[msg("The token is not supported or is currently disabled.")]
+    TokenNotSupported,
+
+    #[msg("The host has no uncured defaults.")]
+    NoActiveDefault,
+
+    // --- Exposure Limit Errors ---
+    #[msg("The loan exceeds the single-booking principal cap.")]
+    BookingExposureExceeded,
+
+    #[msg("The loan would push the host over its exposure cap.")]
+    HostExposureExceeded,
+
+    #[msg("The loan would push the integrator over its exposure cap.")]
+    IntegratorExposureExceeded,
+
+    // --- Pause Errors ---
+    #[msg("This operation is currently paused.")]
+    ProtocolPaused,
+
+    #[msg("The signer is neither the guardian nor the protocol admin.")]
+    NotGuardian,
+
+    #[msg("The pause mask contains unknown flags.")]
+    InvalidPauseMask,
+
+    // --- Timelock Errors ---
+    #[msg("The timelock for this change has not yet expired.")]
+    TimelockNotExpired,
+
+    #[msg("The change targets an account that was not provided or does not match.")]
+    ChangeTargetMismatch,
+
+    // --- Multisig Errors ---
+    #[msg("The multisig threshold must be between 1 and the number of signers.")]
+    InvalidThreshold,
+
+    #[msg("The multisig signer set is empty, too large or has duplicates.")]
+    InvalidSignerSet,
+
+    #[msg("The signer is not a member of the admin multisig.")]
+    NotMultisigMember,
+
+    #[msg("This signer has already approved the proposal.")]
+    AlreadyApproved,
+
+    #[msg("The proposal has not reached the approval threshold.")]
+    ThresholdNotMet,
+
+    #[msg("The proposal has already been executed.")]
+    ProposalAlreadyExecuted,
+
+    #[msg("The accounts supplied do not match the accounts pinned by the proposal.")]
+    ProposalAccountsMismatch,
+
+    // --- Account Versioning Errors ---
+    #[msg("The account uses an outdated layout; run the matching migrate instruction first.")]
+    AccountNotMigrated,
+
+    #[msg("The account is already on the current layout version.")]
+    AlreadyMigrated,
+
+    // --- Liquidity Errors ---
+    #[msg("The pool does not have enough idle liquidity.")]
+    InsufficientLiquidity,
+
+    // --- Repayment Escrow Errors ---
+    #[msg("The repayment escrow can only be funded before the booking ends.")]
+    RepaymentWindowClosed,
+
+    #[msg("The repayment escrow does not cover the debt plus the keeper tip.")]
+    InsufficientEscrow,
+
+    #[msg("The obligation has no funded loan to repay.")]
+    LoanNotFunded,
+
+    #[msg("The repayment escrow stays committed while the loan is active.")]
+    EscrowStillCommitted,
+
+    // --- Default Lifecycle Errors ---
+    #[msg("The grace period is negative or exceeds the maximum.")]
+    InvalidGracePeriod,
+
+    #[msg("Only the admin can write off a defaulted obligation before the write-off delay.")]
+    WriteOffNotDue,
+
+    #[msg("The host's redemption window is still open.")]
+    RedemptionWindowOpen,
+
+    #[msg("The redemption window has closed.")]
+    RedemptionWindowClosed,
+
+    // --- Proof Freshness Errors ---
+    #[msg("The proof is expired, not yet valid, or its validity window is too long.")]
+    ProofNotFresh,
+
+    #[msg("The oracle nonce has already been used.")]
+    NonceAlreadyUsed,
+
+    #[msg("The booking dates are invalid (start must precede end, end must be in the future).")]
+    InvalidBookingDates,
+
+    #[msg("The booking id exceeds 32 bytes.")]
+    BookingIdTooLong,
+
+    #[msg("The booking duration does not match its profit tier.")]
+    TierDurationMismatch,
+
+    // --- Proof Schema Errors ---
+    #[msg("The proof schema version is not supported.")]
+    UnsupportedProofVersion,
+
+    #[msg("The proof extension area is malformed or carries an unknown critical extension.")]
+    MalformedProofExtensions,
+
+    // --- Price Feed Errors ---
+    #[msg("The currency code must be three uppercase ASCII letters.")]
+    InvalidCurrencyCode,
+
+    #[msg("The price feed account is not the registered feed or is malformed.")]
+    InvalidPriceFeed,
+
+    #[msg("A price feed for the booking or loan currency is required.")]
+    MissingPriceFeed,
+
+    #[msg("The price is older than the feed's staleness limit.")]
+    StalePrice,
+
+    #[msg("The price confidence interval is too wide.")]
+    PriceConfidenceTooWide,
+
+    #[msg("The price feed staleness or confidence limit is out of range.")]
+    InvalidPriceFeedConfig,
+
+    // --- Token Extension Errors ---
+    #[msg("The mint has a Token-2022 extension the pool cannot safely hold.")]
+    UnsupportedMintExtension,
+
+    // --- Batch Minting Errors ---
+    #[msg("The batch is empty or holds more bookings than allowed.")]
+    InvalidBatchSize,
+
+    #[msg("The remaining accounts do not match the bookings in the batch.")]
+    BatchAccountsMismatch,
+
+    #[msg("Not enough compute left to mint the next booking; send a smaller batch.")]
+    BatchComputeExhausted,
+
+    // --- Merkle Root Errors ---
+    #[msg("The leaf count or expiry of the booking root is out of range.")]
+    InvalidBookingRoot,
+
+    #[msg("The Merkle proof does not place this booking under the posted root.")]
+    InvalidMerkleProof,
+
+    #[msg("This leaf of the booking root has already been minted.")]
+    LeafAlreadyClaimed,
+
+    #[msg("The booking root has expired.")]
+    BookingRootExpired,
+
+    #[msg("The booking root has not expired yet.")]
+    BookingRootNotExpired,
+
+    // --- Property Errors ---
+    #[msg("The listing id or region is empty or too long.")]
+    InvalidPropertyDetails,
+
+    #[msg("The property does not belong to this integrator and host, or to this booking.")]
+    PropertyMismatch,
+
+    #[msg("The obligation is linked to a property that was not provided.")]
+    MissingProperty,
+
+    #[msg("The property has an unresolved default and cannot back new loans.")]
+    PropertyInDefault,
+
+    #[msg("The host has registered properties; only bookings linked to one can be borrowed against.")]
+    UnlinkedBooking,
+
+    // --- Repayment Escrow Funder Errors ---
+    #[msg("Only the host or the vouching integrator may fund the repayment escrow.")]
+    NotEscrowFunder,
+
+    #[msg("The integrator's token account is required to refund its share of the escrow.")]
+    MissingIntegratorTokenAccount,
 }
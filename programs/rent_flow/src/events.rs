@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminAction, ParamChange};
+
+#[event]
+pub struct ChangeQueued {
+    pub id: u64,
+    pub change: ParamChange,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ChangeExecuted {
+    pub id: u64,
+    pub change: ParamChange,
+}
+
+#[event]
+pub struct ChangeCancelled {
+    pub id: u64,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct AdminProposalCreated {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct AdminProposalApproved {
+    pub id: u64,
+    pub signer: Pubkey,
+    pub approvals: u32,
+}
+
+#[event]
+pub struct AdminProposalExecuted {
+    pub id: u64,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct BookingSettled {
+    pub obligation: Pubkey,
+    pub payer: Pubkey, // Host, or whoever repaid on its behalf
+    pub principal: u64,
+    pub interest: u64,
+    pub penalty: u64,
+    pub protocol_fee: u64,
+    pub reserve_cut: u64,
+    pub lp_yield: u64,
+}
+
+#[event]
+pub struct LossRealized {
+    pub obligation: Pubkey,
+    pub claim: u64,           // Pool claim: debt + bounty the pool advanced
+    pub recovered: u64,       // Auction proceeds paid into the pool (0 on write-off)
+    pub bond_slashed: u64,
+    pub reserve_covered: u64,
+    pub lp_loss: u64,         // Written off LP principal pro-rata
+}
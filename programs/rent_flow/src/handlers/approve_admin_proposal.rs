@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::events::AdminProposalApproved;
+use crate::state::{AdminMultisig, AdminProposal};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ApproveAdminProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin_multisig"],
+        bump = multisig.bump,
+        constraint = multisig.signer_index(&signer.key()).is_some() @ ErrorCode::NotMultisigMember,
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalAlreadyExecuted,
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+}
+
+pub fn handler(ctx: Context<ApproveAdminProposal>) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let bit = 1u16 << ctx.accounts.multisig.signer_index(&signer).ok_or(ErrorCode::NotMultisigMember)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(proposal.approvals & bit == 0, ErrorCode::AlreadyApproved);
+    proposal.approvals |= bit;
+
+    let approvals = proposal.approval_count();
+    emit!(AdminProposalApproved { id: proposal.id, signer, approvals });
+    msg!("Admin Proposal #{} Approved: {}/{}", proposal.id, approvals, ctx.accounts.multisig.threshold);
+    Ok(())
+}
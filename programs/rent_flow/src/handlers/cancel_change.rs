@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::events::ChangeCancelled;
+use crate::state::{PendingChange, ProtocolConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CancelChange<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = authority.key() == protocol_config.admin
+            || authority.key() == protocol_config.guardian @ ErrorCode::NotGuardian,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_change", pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump,
+        close = proposer,
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    /// CHECK: Rent refund destination, pinned to the original proposer
+    #[account(mut, address = pending_change.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelChange>) -> Result<()> {
+    let id = ctx.accounts.pending_change.id;
+
+    emit!(ChangeCancelled { id, cancelled_by: ctx.accounts.authority.key() });
+    msg!("Change #{} Cancelled", id);
+    Ok(())
+}
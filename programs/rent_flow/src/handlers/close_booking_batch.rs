@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::constants::INTEGRATOR_VERSION;
+use crate::error::ErrorCode;
+use crate::state::{BookingBatch, IntegratorConfig};
+
+/// Returns an expired root's rent to the integrator; unclaimed leaves can no longer mint.
+#[derive(Accounts)]
+pub struct CloseBookingBatch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        has_one = authority @ ErrorCode::IntegratorNotAuthorized,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"booking_batch", integration_config.key().as_ref(), &booking_batch.batch_id.to_le_bytes()],
+        bump = booking_batch.bump,
+    )]
+    pub booking_batch: Account<'info, BookingBatch>,
+}
+
+pub fn handler(ctx: Context<CloseBookingBatch>) -> Result<()> {
+    let batch = &ctx.accounts.booking_batch;
+    require!(Clock::get()?.unix_timestamp > batch.expires_at, ErrorCode::BookingRootNotExpired);
+
+    msg!("Booking Root Closed: batch {} ({} of {} leaves minted)", batch.batch_id, batch.claimed_count, batch.leaf_count);
+    Ok(())
+}
@@ -0,0 +1,296 @@
+/*
+  Crank Settle ⚙️
+
+  Hosts (or their integrator) pre-fund a repayment escrow before end_date. Once
+  the booking ends, any keeper can crank the settlement: the escrow pays the usual
+  waterfall, the keeper earns a small tip, the remainder goes back to the funders
+  pro rata and the NFT is released to `host_wallet`. No host signature needed, so
+  loans close on time.
+*/
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{self, Token2022},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+use crate::events::BookingSettled;
+use crate::handlers::settle_booking::settlement_split;
+use crate::state::{BookingObligation, HostProfile, IntegratorConfig, ObligationStatus, Property, ProtocolConfig, ReserveFund, SupportedToken};
+use crate::constants::{INTEGRATOR_VERSION, KEEPER_TIP_BPS, OBLIGATION_VERSION, PAUSE_SETTLE, SUPPORTED_TOKEN_VERSION};
+use crate::error::ErrorCode;
+use crate::token_extensions::{gross_up, withheld_fees};
+use crate::math::bps_of;
+
+#[derive(Accounts)]
+pub struct CrankSettle<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = !obligation.is_settled @ ErrorCode::AlreadySettled,
+        constraint = obligation.has_active_loan() @ ErrorCode::LoanNotFunded,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
+
+    /// CHECK: Receives the NFT, the escrow remainder and the escrow rent
+    #[account(mut, address = obligation.host_wallet @ ErrorCode::NotAuthorizedOwner)]
+    pub host_wallet: UncheckedAccount<'info>,
+
+    // --- Escrow Corridor ---
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::authority = obligation,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = loan_mint,
+    )]
+    pub keeper_token_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::authority = host_wallet,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub host_token_ata: InterfaceAccount<'info, TokenAccount>,
+    // Required when the integrator funded part of the escrow
+    #[account(
+        mut,
+        token::mint = loan_mint,
+        token::authority = integration_config.authority,
+    )]
+    pub integrator_token_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // --- Waterfall Destinations ---
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_SETTLE) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        associated_token::authority = protocol_config,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"host_profile", obligation.host_wallet.as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
+
+    // --- Asset Release Corridor (Vault -> Host) ---
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub vault_nft_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::authority = host_wallet,
+        associated_token::mint = nft_mint,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub host_nft_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,   // For the loan currency
+    pub token_2022_program: Program<'info, Token2022>,     // For NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CrankSettle>) -> Result<()> {
+    // 1. DUE CHECK: Cranks only settle matured bookings (never an early exit)
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.obligation.end_date, ErrorCode::BookingNotYetEnded);
+
+    let split = settlement_split(&ctx.accounts.obligation, &ctx.accounts.protocol_config, now)?;
+    let keeper_tip = bps_of(split.principal, KEEPER_TIP_BPS)?;
+    let total = split.total()?;
+
+    // Transfer-fee mints: protocol legs are grossed up so each lands in full;
+    // the keeper and the host bear the fee on what they are paid
+    let loan_mint = ctx.accounts.loan_mint.to_account_info();
+    let pool_leg = gross_up(&loan_mint, split.pool_amount()?)?;
+    let fee_leg = gross_up(&loan_mint, split.protocol_fee)?;
+    let reserve_leg = gross_up(&loan_mint, split.reserve_cut)?;
+    let escrowed = ctx.accounts.escrow_token_ata.amount;
+    let refund = escrowed
+        .checked_sub(pool_leg).ok_or(ErrorCode::InsufficientEscrow)?
+        .checked_sub(fee_leg).ok_or(ErrorCode::InsufficientEscrow)?
+        .checked_sub(reserve_leg).ok_or(ErrorCode::InsufficientEscrow)?
+        .checked_sub(keeper_tip).ok_or(ErrorCode::InsufficientEscrow)?;
+
+    let integrator_refund = ctx.accounts.obligation.integrator_escrow_share(refund, escrowed)?;
+    let host_refund = refund - integrator_refund;
+
+    // 2. WATERFALL: Escrow -> Pool / Treasury / Reserve / Keeper / Host + Integrator (remainder)
+    let mint_key = ctx.accounts.nft_mint.key();
+    let bump = ctx.accounts.obligation.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"obligation", mint_key.as_ref(), &[bump]]];
+    let decimals = ctx.accounts.loan_mint.decimals;
+
+    let mut legs = vec![
+        (ctx.accounts.token_vault.to_account_info(), pool_leg),
+        (ctx.accounts.treasury_token_ata.to_account_info(), fee_leg),
+        (ctx.accounts.reserve_token_ata.to_account_info(), reserve_leg),
+        (ctx.accounts.keeper_token_ata.to_account_info(), keeper_tip),
+        (ctx.accounts.host_token_ata.to_account_info(), host_refund),
+    ];
+    if integrator_refund > 0 {
+        let integrator_token_ata = ctx.accounts.integrator_token_ata.as_ref()
+            .ok_or(ErrorCode::MissingIntegratorTokenAccount)?;
+        legs.push((integrator_token_ata.to_account_info(), integrator_refund));
+    }
+    for (destination, amount) in legs {
+        if amount == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.escrow_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.obligation.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            decimals,
+        )?;
+    }
+
+    // The emptied escrow's rent goes back to the host (unless it still holds
+    // withheld transfer fees, which block closing until harvested)
+    if withheld_fees(&ctx.accounts.escrow_token_ata.to_account_info())? == 0 {
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.escrow_token_ata.to_account_info(),
+                destination: ctx.accounts.host_wallet.to_account_info(),
+                authority: ctx.accounts.obligation.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.total_fees_collected = config.total_fees_collected
+        .checked_add(split.protocol_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let reserve = &mut ctx.accounts.reserve_fund;
+    reserve.total_contributed = reserve.total_contributed
+        .checked_add(split.reserve_cut)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // 3. RELEASE: NFT back to the host wallet
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            token_2022::TransferChecked {
+                from: ctx.accounts.vault_nft_ata.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.host_nft_ata.to_account_info(),
+                authority: ctx.accounts.obligation.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1, 0
+    )?;
+
+    // 4. EXIT: Books and reputation
+    let pool = &mut ctx.accounts.supported_token;
+    pool.outstanding_principal = pool.outstanding_principal
+        .checked_sub(split.principal)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.distribute_yield(split.lp_yield)?;
+
+    let integrator = &mut ctx.accounts.integration_config;
+    integrator.outstanding_principal = integrator.outstanding_principal
+        .checked_sub(split.principal)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let obligation = &mut ctx.accounts.obligation;
+    ctx.accounts.host_profile.record_repayment(obligation.end_date, now, split.principal, total)?;
+    if let Some(property) = Property::linked(obligation, &mut ctx.accounts.property)? {
+        property.record_repayment(split.principal)?;
+    }
+
+    obligation.is_settled = true;
+    obligation.is_locked = false;
+    obligation.integrator_escrowed = 0;
+    obligation.status = ObligationStatus::Repaid;
+
+    emit!(BookingSettled {
+        obligation: obligation.key(),
+        payer: obligation.host_wallet, // Escrow funds; the keeper only cranked
+        principal: split.principal,
+        interest: split.interest,
+        penalty: split.penalty,
+        protocol_fee: split.protocol_fee,
+        reserve_cut: split.reserve_cut,
+        lp_yield: split.lp_yield,
+    });
+    msg!(
+        "Settlement Cranked By {}. Keeper Tip: {}, Host Refund: {}, Integrator Refund: {}",
+        ctx.accounts.keeper.key(), keeper_tip, host_refund, integrator_refund
+    );
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_MULTISIG_SIGNERS;
+use crate::state::{AdminMultisig, ProtocolConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AdminMultisig::INIT_SPACE,
+        seeds = [b"admin_multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    /// CHECK: System-owned PDA with no data. It signs (and pays) as `admin` when a
+    /// proposal executes; hand it the admin role with `transfer_admin` and fund it with SOL.
+    #[account(
+        seeds = [b"multisig_authority"],
+        bump
+    )]
+    pub multisig_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+        ErrorCode::InvalidSignerSet
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(!signers[..i].contains(signer), ErrorCode::InvalidSignerSet);
+    }
+    require!(
+        threshold >= 1 && threshold as usize <= signers.len(),
+        ErrorCode::InvalidThreshold
+    );
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.authority_bump = ctx.bumps.multisig_authority;
+    multisig.bump = ctx.bumps.multisig;
+
+    msg!(
+        "Admin Multisig Created: {}-of-{}, authority {}",
+        threshold, multisig.signers.len(), ctx.accounts.multisig_authority.key()
+    );
+    Ok(())
+}
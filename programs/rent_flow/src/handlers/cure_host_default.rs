@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::constants::OBLIGATION_VERSION;
+use crate::state::{BookingObligation, HostProfile, ProtocolConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CureHostDefault<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"host_profile", host_profile.host.as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // The default being cured, so a later redemption of it doesn't clear another one
+    #[account(
+        mut,
+        seeds = [b"obligation", obligation.nft_mint.as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = obligation.host_wallet == host_profile.host @ ErrorCode::NotAuthorizedOwner,
+        constraint = obligation.has_open_default() @ ErrorCode::NoActiveDefault,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
+}
+
+/// Clears one uncured default once the debt was settled off-chain.
+/// The default stays in `default_count`, so it keeps weighing on LTV and rate.
+pub fn handler(ctx: Context<CureHostDefault>) -> Result<()> {
+    let profile = &mut ctx.accounts.host_profile;
+    require!(profile.active_defaults > 0, ErrorCode::NoActiveDefault);
+
+    profile.active_defaults -= 1;
+    ctx.accounts.obligation.default_cured = true;
+
+    msg!("Host Default Cured: {} ({} remaining)", profile.host, profile.active_defaults);
+    Ok(())
+}
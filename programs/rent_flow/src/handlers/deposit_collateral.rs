@@ -4,8 +4,11 @@ use anchor_spl::{
     token_2022::{self, Token2022},
     token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
-use crate::state::BookingObligation;
+use crate::state::{BookingObligation, CurrencyFeed, HostProfile, IntegratorConfig, PoolVault, Property, ProtocolConfig, SupportedToken};
+use crate::constants::{HOST_PROFILE_VERSION, INTEGRATOR_VERSION, OBLIGATION_VERSION, PAUSE_BORROW, POOL_VAULT_VERSION, SUPPORTED_TOKEN_VERSION};
 use crate::error::ErrorCode;
+use crate::math::bps_of;
+use crate::oracle::{self, FeedPrice, USD};
 
 
 #[derive(Accounts)]
@@ -17,17 +20,19 @@ pub struct DepositCollateral<'info> {
         mut,
         seeds = [b"obligation", nft_mint.key().as_ref()],
         bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = obligation.host_wallet == host.key() @ ErrorCode::NotHubOwner,
     )]
     pub obligation: Account<'info, BookingObligation>,
 
     pub nft_mint: InterfaceAccount<'info, Mint>,
-    pub usdc_mint: InterfaceAccount<'info, Mint>, // NEW: Required for decimals check
+    pub loan_mint: InterfaceAccount<'info, Mint>, // The currency the host borrows in
 
     #[account(mut)]
     pub host_nft_ata: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
-    pub host_usdc_ata: InterfaceAccount<'info, TokenAccount>, // NEW: Recipient of funds
+    pub host_token_ata: InterfaceAccount<'info, TokenAccount>, // NEW: Recipient of funds
 
     #[account(
         init_if_needed,
@@ -38,11 +43,79 @@ pub struct DepositCollateral<'info> {
     )]
     pub vault_nft_ata: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub vault_usdc_ata: InterfaceAccount<'info, TokenAccount>, // NEW: Source of liquidity
+    // Rate model and grace periods (shared by every currency)
+    #[account(
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+
+    // Source of liquidity: the investors' pool for this currency, not a per-obligation account
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    // --- Risk Inputs + the currency's lending books ---
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = supported_token.is_active @ ErrorCode::TokenNotSupported,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+
+    #[account(
+        init_if_needed,
+        payer = host,
+        space = 8 + HostProfile::INIT_SPACE,
+        seeds = [b"host_profile", host.key().as_ref()],
+        bump
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_BORROW) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    // --- Price Feeds: only required when the booking is priced in another currency ---
+    #[account(
+        seeds = [b"currency_feed", obligation.booking_currency.as_ref()],
+        bump = booking_currency_feed.bump,
+    )]
+    pub booking_currency_feed: Option<Account<'info, CurrencyFeed>>,
+    /// CHECK: Must be booking_currency_feed.feed; verified by `oracle::load_price`
+    pub booking_price_feed: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"currency_feed", supported_token.currency.as_ref()],
+        bump = loan_currency_feed.bump,
+    )]
+    pub loan_currency_feed: Option<Account<'info, CurrencyFeed>>,
+    /// CHECK: Must be loan_currency_feed.feed; verified by `oracle::load_price`
+    pub loan_price_feed: Option<UncheckedAccount<'info>>,
 
     pub token_2022_program: Program<'info, Token2022>,
-    pub token_program: Interface<'info, TokenInterface>, // For standard SPL USDC
+    pub token_program: Interface<'info, TokenInterface>, // For the loan currency
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -76,12 +149,125 @@ pub struct DepositCollateral<'info> {
 //     Ok(())
 // }
 
+type FeedAccounts<'a, 'info> = (&'a Option<Account<'info, CurrencyFeed>>, &'a Option<UncheckedAccount<'info>>);
+
+/// `booking_value` in loan-currency units. Bookings priced in the loan currency need
+/// no feeds; otherwise both legs go through USD, valued at the conservative end of
+/// each confidence band (collateral low, loan currency high).
+fn collateral_value(
+    obligation: &BookingObligation,
+    token: &SupportedToken,
+    [booking_feed, loan_feed]: [FeedAccounts; 2],
+) -> Result<u64> {
+    let booking_currency = obligation.booking_currency;
+    if booking_currency == [0; 3] || booking_currency == token.currency {
+        return Ok(obligation.booking_value);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let booking_price = usd_price(booking_currency, booking_feed, now)?.lower();
+    let loan_price = usd_price(token.currency, loan_feed, now)?.upper()?;
+    let value = oracle::convert(obligation.booking_value, &booking_price, &loan_price)?;
+
+    msg!(
+        "Booking Valued: {} {} = {} in the loan currency",
+        obligation.booking_value, String::from_utf8_lossy(&booking_currency), value
+    );
+    Ok(value)
+}
+
+/// USD price of `currency` from its registered feed; USD itself is 1.
+fn usd_price(currency: [u8; 3], (config, feed): FeedAccounts, now: i64) -> Result<FeedPrice> {
+    if currency == USD {
+        return Ok(FeedPrice::ONE_USD);
+    }
+    match (config, feed) {
+        (Some(config), Some(feed)) => oracle::load_price(&feed.to_account_info(), config, now),
+        _ => err!(ErrorCode::MissingPriceFeed),
+    }
+}
+
 pub fn handler(ctx: Context<DepositCollateral>, funding_amount: u64) -> Result<()> {
     let obligation = &mut ctx.accounts.obligation;
     
     // 🚩 SAFETY SENSOR: Prevent redundant deposit
     require!(!obligation.is_locked, ErrorCode::AlreadyLocked);
 
+    // 🚩 REPUTATION SENSOR: Uncured defaults block new borrowing
+    let profile = &mut ctx.accounts.host_profile;
+    if profile.host == Pubkey::default() {
+        profile.host = ctx.accounts.host.key();
+        profile.bump = ctx.bumps.host_profile;
+        profile.version = HOST_PROFILE_VERSION;
+    }
+    require!(!profile.is_blocked(), ErrorCode::HostBlocked);
+
+    // 🚩 PROPERTY SENSOR: A host with registered listings only borrows against bookings linked to one,
+    //    so a listing's default history can't be dodged by minting unlinked
+    require!(
+        obligation.property != Pubkey::default() || profile.registered_properties == 0,
+        ErrorCode::UnlinkedBooking
+    );
+
+    // 🚩 PROPERTY SENSOR: A listing with an unresolved default backs no new loans
+    if let Some(property) = Property::linked(obligation, &mut ctx.accounts.property)? {
+        require!(property.open_defaults == 0, ErrorCode::PropertyInDefault);
+    }
+
+    // 💱 VALUATION: The booking's value in loan-currency units
+    let collateral_value = collateral_value(
+        obligation,
+        &ctx.accounts.supported_token,
+        [
+            (&ctx.accounts.booking_currency_feed, &ctx.accounts.booking_price_feed),
+            (&ctx.accounts.loan_currency_feed, &ctx.accounts.loan_price_feed),
+        ],
+    )?;
+
+    // 🚩 AMOUNT SENSOR: A zero-principal loan could never be settled or liquidated,
+    //    stranding the NFT in the pool
+    require!(funding_amount > 0, ErrorCode::InvalidAmount);
+
+    // 📐 LTV: Token base LTV, adjusted by the host's track record
+    let ltv_bps = profile.adjusted_ltv_bps(ctx.accounts.supported_token.ltv_bps as u64);
+    let max_principal = bps_of(collateral_value, ltv_bps)?;
+    require!(funding_amount <= max_principal, ErrorCode::ExceedsLtv);
+    obligation.max_principal = max_principal;
+
+    // 🚩 LIQUIDITY SENSOR: Only idle LP funds can be lent
+    require!(
+        funding_amount <= ctx.accounts.supported_token.available_liquidity()
+            && funding_amount <= ctx.accounts.token_vault.amount,
+        ErrorCode::InsufficientLiquidity
+    );
+
+    // 🚩 CONCENTRATION SENSOR: No single booking, host or integrator may dominate the pool
+    // Host / integrator exposure is summed at face value across currencies (stablecoins)
+    let host_after = profile.outstanding_principal
+        .checked_add(funding_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let integrator_after = ctx.accounts.integration_config.outstanding_principal
+        .checked_add(funding_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.protocol_config.exposure_limits.check(
+        ctx.accounts.supported_token.total_liquidity_tracked,
+        funding_amount,
+        host_after,
+        integrator_after,
+    )?;
+
+    // 📈 PRICING: Lock the utilization-curve rate (after reputation) for the life of the loan
+    let pool_vault = &ctx.accounts.pool_vault;
+    let utilization = ctx.accounts.supported_token.utilization_bps(funding_amount)?;
+    let market_rate = pool_vault.rate_model.borrow_rate_bps(utilization)?;
+    obligation.rate_bps = profile.adjusted_rate_bps(market_rate);
+
+    // ⏳ GRACE: Snapshot the effective grace period so later config changes can't shorten it
+    obligation.grace_period = pool_vault.grace_period_for(
+        obligation.tier,
+        ctx.accounts.integration_config.grace_period,
+    );
+
     // --- PART 1: LOCK COLLATERAL (Host -> Vault) ---
     token_2022::transfer_checked(
         CpiContext::new(
@@ -97,33 +283,53 @@ pub fn handler(ctx: Context<DepositCollateral>, funding_amount: u64) -> Result<(
     )?;
 
     // --- PART 2: DISBURSE FUNDING (Vault -> Host) ---
-    // RULE: Since the Vault (PDA) is the authority, we need signer seeds.
-    let mint_key = ctx.accounts.nft_mint.key();
-    let signer_seeds: &[&[&[u8]]] = &[&[
-        b"obligation",
-        mint_key.as_ref(),
-        &[ctx.accounts.obligation.bump],
-    ]];
+    // RULE: Since the SupportedToken (PDA) is the authority, we need signer seeds.
+    // The debt is the full amount sent; a transfer-fee mint's fee is the host's to bear.
+    let loan_mint_key = ctx.accounts.loan_mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", loan_mint_key.as_ref(), &[ctx.accounts.supported_token.bump]]];
 
     token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             token_interface::TransferChecked {
-                from: ctx.accounts.vault_usdc_ata.to_account_info(),
-                mint: ctx.accounts.usdc_mint.to_account_info(),
-                to: ctx.accounts.host_usdc_ata.to_account_info(),
-                authority: ctx.accounts.obligation.to_account_info(),
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.loan_mint.to_account_info(),
+                to: ctx.accounts.host_token_ata.to_account_info(),
+                authority: ctx.accounts.supported_token.to_account_info(),
             },
             signer_seeds
         ),
         funding_amount,
-        ctx.accounts.usdc_mint.decimals
+        ctx.accounts.loan_mint.decimals
     )?;
 
     // --- PART 3: STATE UPDATE ---
     let obligation = &mut ctx.accounts.obligation;
     obligation.is_locked = true;
-    // obligation.loan_amount = funding_amount; // Track the debt for settlement
+    obligation.borrowed_amount = funding_amount; // Track the debt for settlement
+    obligation.loan_mint = loan_mint_key;        // ...and the currency it must be repaid in
+
+    let pool = &mut ctx.accounts.supported_token;
+    pool.outstanding_principal = pool.outstanding_principal
+        .checked_add(funding_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let integrator = &mut ctx.accounts.integration_config;
+    integrator.outstanding_principal = integrator.outstanding_principal
+        .checked_add(funding_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if let Some(property) = Property::linked(obligation, &mut ctx.accounts.property)? {
+        property.record_funding(funding_amount)?;
+    }
+
+    let profile = &mut ctx.accounts.host_profile;
+    profile.total_borrowed = profile.total_borrowed
+        .checked_add(funding_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    profile.outstanding_principal = profile.outstanding_principal
+        .checked_add(funding_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::constants::{PAUSE_LP_DEPOSIT, POSITION_VERSION, SUPPORTED_TOKEN_VERSION};
+use crate::state::{Position, ProtocolConfig, SupportedToken};
+use crate::error::ErrorCode;
+use crate::token_extensions::net_of_fee;
+
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", investor.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    // The currency's lending pool: books here, cash in its vault
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = supported_token.is_active @ ErrorCode::TokenNotSupported,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_LP_DEPOSIT) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        associated_token::authority = investor,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub investor_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // 1. Move the tokens into the currency's vault the hosts borrow from
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.investor_token_ata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.investor.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    // Transfer-fee mints withhold part of it in the vault; only what lands is credited
+    let amount = net_of_fee(&ctx.accounts.mint.to_account_info(), amount)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // 2. Credit the investor's position in this currency (created on first deposit)
+    let pool = &ctx.accounts.supported_token;
+    let position = &mut ctx.accounts.position;
+    if position.owner == Pubkey::default() {
+        position.owner = ctx.accounts.investor.key();
+        position.bump = ctx.bumps.position;
+        position.version = POSITION_VERSION;
+        position.checkpoint(pool)?;
+    }
+    require!(position.version == POSITION_VERSION, ErrorCode::AccountNotMigrated);
+    position.harvest(pool)?; // Bank yield and losses on the old principal first
+    position.principal = position.principal
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    position.checkpoint(pool)?;
+
+    // 3. Grow the pool's books
+    let pool = &mut ctx.accounts.supported_token;
+    pool.total_liquidity_tracked = pool.total_liquidity_tracked
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.total_deposits = pool.total_deposits
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Liquidity Deposited: {} of {}", amount, pool.mint);
+    Ok(())
+}
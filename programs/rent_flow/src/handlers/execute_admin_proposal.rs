@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use crate::events::AdminProposalExecuted;
+use crate::program::RentFlow;
+use crate::state::{AdminMultisig, AdminProposal};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ExecuteAdminProposal<'info> {
+    // Permissionless: once the threshold is reached anyone can land the proposal
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin_multisig"],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalAlreadyExecuted,
+        constraint = proposal.approval_count() >= multisig.threshold as u32 @ ErrorCode::ThresholdNotMet,
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    /// CHECK: System-owned PDA that signs as `admin` inside the executed instruction
+    #[account(
+        mut,
+        seeds = [b"multisig_authority"],
+        bump = multisig.authority_bump,
+    )]
+    pub multisig_authority: UncheckedAccount<'info>,
+
+    pub rent_flow_program: Program<'info, RentFlow>,
+    // remaining_accounts: exactly `proposal.accounts`, in order
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteAdminProposal<'info>>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let authority = ctx.accounts.multisig_authority.key();
+    let remaining = ctx.remaining_accounts;
+
+    // 1. PIN CHECK: The executor cannot swap in different targets than the signers approved
+    require!(remaining.len() == proposal.accounts.len(), ErrorCode::ProposalAccountsMismatch);
+    let mut metas = Vec::with_capacity(remaining.len());
+    for (info, pinned) in remaining.iter().zip(proposal.accounts.iter()) {
+        require_keys_eq!(info.key(), pinned.pubkey, ErrorCode::ProposalAccountsMismatch);
+        let is_signer = info.is_signer || info.key() == authority;
+        metas.push(if pinned.is_writable {
+            AccountMeta::new(pinned.pubkey, is_signer)
+        } else {
+            AccountMeta::new_readonly(pinned.pubkey, is_signer)
+        });
+    }
+
+    // 2. SELF-CPI: Run the admin instruction with the multisig authority as `admin`
+    let ix = Instruction {
+        program_id: crate::ID,
+        accounts: metas,
+        data: proposal.action.instruction_data(),
+    };
+    let mut infos = remaining.to_vec();
+    infos.push(ctx.accounts.rent_flow_program.to_account_info());
+
+    let authority_seeds: &[&[&[u8]]] = &[&[b"multisig_authority", &[ctx.accounts.multisig.authority_bump]]];
+    invoke_signed(&ix, &infos, authority_seeds)?;
+
+    // 3. EXIT
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.executed = true;
+
+    emit!(AdminProposalExecuted { id: proposal.id, action: proposal.action });
+    msg!("Admin Proposal #{} Executed", proposal.id);
+    Ok(())
+}
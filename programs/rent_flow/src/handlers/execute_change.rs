@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::constants::{INTEGRATOR_VERSION, POOL_VAULT_VERSION, SUPPORTED_TOKEN_VERSION};
+use crate::events::ChangeExecuted;
+use crate::state::{IntegratorConfig, ParamChange, PendingChange, PoolVault, ProtocolConfig, SupportedToken};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ExecuteChange<'info> {
+    // Permissionless: anyone can land a change once its timelock has expired
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_change", pending_change.id.to_le_bytes().as_ref()],
+        bump = pending_change.bump,
+        close = proposer,
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    /// CHECK: Rent refund destination, pinned to the original proposer
+    #[account(mut, address = pending_change.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+
+    // Only required for TokenLtv changes
+    #[account(mut)]
+    pub supported_token: Option<Account<'info, SupportedToken>>,
+
+    // Only required for IntegratorGracePeriod changes
+    #[account(mut)]
+    pub integration_config: Option<Account<'info, IntegratorConfig>>,
+}
+
+pub fn handler(ctx: Context<ExecuteChange>) -> Result<()> {
+    let pending = &ctx.accounts.pending_change;
+    require!(
+        Clock::get()?.unix_timestamp >= pending.eta,
+        ErrorCode::TimelockNotExpired
+    );
+
+    let config = &mut ctx.accounts.protocol_config;
+    match pending.change {
+        ParamChange::TokenLtv { mint, ltv_bps } => {
+            let token = ctx.accounts.supported_token.as_mut()
+                .ok_or(ErrorCode::ChangeTargetMismatch)?;
+            let (expected, _) = Pubkey::find_program_address(&[b"vault", mint.as_ref()], ctx.program_id);
+            require_keys_eq!(token.key(), expected, ErrorCode::ChangeTargetMismatch);
+            require!(token.version == SUPPORTED_TOKEN_VERSION, ErrorCode::AccountNotMigrated);
+            token.ltv_bps = ltv_bps;
+        }
+        ParamChange::ReserveBps { reserve_bps } => {
+            config.reserve_bps = reserve_bps;
+        }
+        ParamChange::ProtocolFeeBps { fee_bps } => {
+            config.protocol_fee_bps = fee_bps;
+        }
+        ParamChange::BondParams { min_bond_amount, min_bond_bps, slash_bps, unbonding_period } => {
+            config.min_bond_amount = min_bond_amount;
+            config.min_bond_bps = min_bond_bps;
+            config.slash_bps = slash_bps;
+            config.unbonding_period = unbonding_period;
+        }
+        ParamChange::ExposureLimits { limits } => {
+            config.exposure_limits = limits;
+        }
+        ParamChange::GracePeriods { pool, tiers } => {
+            // Only affects loans funded afterwards; live loans keep their snapshot
+            let pool_vault = &mut ctx.accounts.pool_vault;
+            pool_vault.grace_period = pool;
+            pool_vault.tier_grace_periods = tiers;
+        }
+        ParamChange::IntegratorGracePeriod { integrator, grace_period } => {
+            let target = ctx.accounts.integration_config.as_mut()
+                .ok_or(ErrorCode::ChangeTargetMismatch)?;
+            require_keys_eq!(target.key(), integrator, ErrorCode::ChangeTargetMismatch);
+            require!(target.version == INTEGRATOR_VERSION, ErrorCode::AccountNotMigrated);
+            target.grace_period = grace_period;
+        }
+        ParamChange::RateModel { rate_model } => {
+            // Only affects loans funded afterwards; live loans keep their locked rate
+            ctx.accounts.pool_vault.rate_model = rate_model;
+        }
+        ParamChange::TimelockDelay { delay } => {
+            config.timelock_delay = delay;
+        }
+        ParamChange::Oracle { oracle } => {
+            config.oracle = oracle;
+        }
+        ParamChange::LiquidationBounty { bounty } => {
+            config.liquidation_bounty = bounty;
+        }
+        ParamChange::Redemption { terms } => {
+            config.redemption = terms;
+        }
+    }
+
+    emit!(ChangeExecuted { id: pending.id, change: pending.change });
+    msg!("Change #{} Executed: {:?}", pending.id, pending.change);
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+use crate::state::{BookingObligation, IntegratorConfig};
+use crate::constants::{INTEGRATOR_VERSION, OBLIGATION_VERSION};
+use crate::error::ErrorCode;
+use crate::token_extensions::net_of_fee;
+
+#[derive(Accounts)]
+pub struct FundRepaymentEscrow<'info> {
+    // The host or the vouching integrator: whatever is left is refunded to them pro rata
+    #[account(
+        mut,
+        constraint = funder.key() == obligation.host_wallet
+            || funder.key() == integration_config.authority @ ErrorCode::NotEscrowFunder,
+    )]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = !obligation.is_settled @ ErrorCode::AlreadySettled,
+        constraint = obligation.has_active_loan() @ ErrorCode::LoanNotFunded,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
+
+    #[account(
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>, // Repaid in the currency that was lent
+
+    #[account(mut)]
+    pub funder_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // Escrow: owned by the obligation PDA, drained by `crank_settle`, or refunded to its
+    // funders by `withdraw_repayment_escrow` once the loan closes another way
+    #[account(
+        init_if_needed,
+        payer = funder,
+        associated_token::authority = obligation,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundRepaymentEscrow>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        Clock::get()?.unix_timestamp < ctx.accounts.obligation.end_date,
+        ErrorCode::RepaymentWindowClosed
+    );
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.funder_token_ata.to_account_info(),
+                mint: ctx.accounts.loan_mint.to_account_info(),
+                to: ctx.accounts.escrow_token_ata.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.loan_mint.decimals,
+    )?;
+
+    // Transfer-fee mints: the escrow holds what lands, and crank_settle grosses up from that
+    let received = net_of_fee(&ctx.accounts.loan_mint.to_account_info(), amount)?;
+    let obligation = &mut ctx.accounts.obligation;
+    if ctx.accounts.funder.key() != obligation.host_wallet {
+        obligation.integrator_escrowed = obligation.integrator_escrowed
+            .checked_add(received)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    msg!("Repayment Escrow Funded: {} ({} received)", amount, received);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use crate::constants::{BPS_DENOMINATOR, DEFAULT_BOUNTY_BPS, DEFAULT_BOUNTY_CAP, DEFAULT_BOUNTY_FIXED, DEFAULT_MIN_BOND_BPS, DEFAULT_PROTOCOL_FEE_BPS, DEFAULT_REDEMPTION_PENALTY_BPS, DEFAULT_REDEMPTION_WINDOW, DEFAULT_SLASH_BPS, DEFAULT_TIMELOCK_DELAY, DEFAULT_UNBONDING_PERIOD, PROTOCOL_CONFIG_VERSION, RESERVE_FUND_VERSION};
+use crate::state::{LiquidationBounty, ProtocolConfig, RedemptionTerms, ReserveFund};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProtocolConfig::INIT_SPACE,
+        seeds = [b"protocol_config"],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ReserveFund::INIT_SPACE,
+        seeds = [b"reserve_fund"],
+        bump
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    // The reserve's cash box: owned by the ReserveFund PDA
+    #[account(
+        init,
+        payer = admin,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = usdc_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // The treasury's cash box: owned by the ProtocolConfig PDA
+    #[account(
+        init,
+        payer = admin,
+        associated_token::authority = protocol_config,
+        associated_token::mint = usdc_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeProtocol>, reserve_bps: u16) -> Result<()> {
+    require!(reserve_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.admin = ctx.accounts.admin.key();
+    config.guardian = ctx.accounts.admin.key(); // Hand off to a dedicated key via set_guardian
+    config.oracle = ctx.accounts.admin.key();   // Rotate to the attestation service via ParamChange::Oracle
+    config.reserve_bps = reserve_bps;
+    config.protocol_fee_bps = DEFAULT_PROTOCOL_FEE_BPS;
+    config.min_bond_bps = DEFAULT_MIN_BOND_BPS;
+    config.slash_bps = DEFAULT_SLASH_BPS;
+    config.unbonding_period = DEFAULT_UNBONDING_PERIOD;
+    config.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+    config.liquidation_bounty = LiquidationBounty {
+        fixed: DEFAULT_BOUNTY_FIXED,
+        bps: DEFAULT_BOUNTY_BPS,
+        cap: DEFAULT_BOUNTY_CAP,
+    };
+    config.redemption = RedemptionTerms {
+        window: DEFAULT_REDEMPTION_WINDOW,
+        penalty_bps: DEFAULT_REDEMPTION_PENALTY_BPS,
+    };
+    config.bump = ctx.bumps.protocol_config;
+    config.version = PROTOCOL_CONFIG_VERSION;
+
+    let reserve = &mut ctx.accounts.reserve_fund;
+    reserve.bump = ctx.bumps.reserve_fund;
+    reserve.version = RESERVE_FUND_VERSION;
+
+    msg!("Protocol Initialized. Admin: {}, Reserve Slice: {} bps", config.admin, reserve_bps);
+    Ok(())
+}
@@ -4,8 +4,13 @@
   our program to interact with both Token and Token-2022 programs.
 */
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{ Mint, TokenAccount, TokenInterface };
-use crate::state::SupportedToken;
+use crate::constants::{SUPPORTED_TOKEN_VERSION, YIELD_PRECISION};
+use crate::state::{ProtocolConfig, ReserveFund, SupportedToken};
+use crate::error::ErrorCode;
+use crate::oracle::validate_currency_code;
+use crate::token_extensions::validate_lending_mint;
 
 /* STEP 2: Define the Validation Struct (InitializeSupportedToken)
   We use Interface types to ensure compatibility with Token-2022 extensions.
@@ -25,11 +30,13 @@ use crate::state::SupportedToken;
      - 'seeds': Hardcoded string + the mint's public key for uniqueness.
      
   D. The Token Vault (InterfaceAccount<'info, TokenAccount>):
-     - 'init': Creates the vault.
-     - 'token::mint': Links the vault to our mint.
-     - 'token::authority': Sets the 'supported_token' PDA as the owner.
-     - 'token::token_program': CRITICAL. Links the vault to the specific 
+     - 'init': Creates the vault: this currency's lending pool cash.
+     - 'associated_token::mint': Links the vault to our mint.
+     - 'associated_token::authority': Sets the 'supported_token' PDA as the owner,
+        so every flow can derive the vault from the mint alone.
+     - 'associated_token::token_program': CRITICAL. Links the vault to the specific
         program (Token or Token-2022) identified in Step E.
+     The treasury and reserve fund get their cash box for the new currency too.
      
   E. The Flexible Token Program:
      Using 'Interface<'info, TokenInterface>' allows the caller to pass 
@@ -39,6 +46,15 @@ use crate::state::SupportedToken;
 pub struct InitializeSupportedToken<'info> {
     #[account(mut)]
     admin: Signer<'info>,
+
+    // Listing is an admin action (single key or the multisig authority PDA)
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     mint: InterfaceAccount<'info, Mint>,
     #[account(
         init,
@@ -55,13 +71,38 @@ pub struct InitializeSupportedToken<'info> {
     #[account(
         init,
         payer = admin,
-        token::mint = mint,
-        token::authority = supported_token, // The PDA controls the funds 🏦
-        token::token_program = flexible_token_program
+        associated_token::mint = mint,
+        associated_token::authority = supported_token, // The PDA controls the funds 🏦
+        associated_token::token_program = flexible_token_program
     )]
     pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = protocol_config,
+        associated_token::token_program = flexible_token_program
+    )]
+    pub treasury_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = reserve_fund,
+        associated_token::token_program = flexible_token_program
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
     pub flexible_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -75,10 +116,17 @@ pub struct InitializeSupportedToken<'info> {
      - Set 'supported_token.ltv_bps' from the instruction argument.
      - Set 'supported_token.bump' from the context.
      - Set 'supported_token.is_active' to true.
+     - Set 'supported_token.currency' (ISO code the token tracks, e.g. "USD").
      
   C. Exit: return Ok(());
+
+  Token-2022 mints are screened first: a permanent delegate could drain the
+  vault and a non-transferable mint could never be paid back out.
 */
-pub fn handler(ctx: Context<InitializeSupportedToken>, ltv_bps: u16) -> Result<()> {
+pub fn handler(ctx: Context<InitializeSupportedToken>, ltv_bps: u16, currency: [u8; 3]) -> Result<()> {
+    // 0. Refuse mints whose extensions put the vault at the issuer's mercy
+    validate_lending_mint(&ctx.accounts.mint.to_account_info())?;
+
     // 1. Reference the account from the context
     let supported_token = &mut ctx.accounts.supported_token;
     
@@ -86,9 +134,12 @@ pub fn handler(ctx: Context<InitializeSupportedToken>, ltv_bps: u16) -> Result<(
     supported_token.mint = ctx.accounts.mint.key();
     supported_token.ltv_bps = ltv_bps; // Use the passed argument!
     supported_token.is_active = true;
+    supported_token.currency = validate_currency_code(&currency)?; // Prices bookings via CurrencyFeed
     
     // 3. Access the specific bump discovered by Anchor
     supported_token.bump = ctx.bumps.supported_token;
+    supported_token.version = SUPPORTED_TOKEN_VERSION;
+    supported_token.loss_index = YIELD_PRECISION; // Empty lending pool, no losses yet
     
     Ok(())
 }
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{IntegratorConfig, PoolVault};
+use crate::constants::*;
+use crate::state::{IntegratorConfig, PoolVault, RateModel};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -34,12 +35,28 @@ pub fn handler(ctx: Context<Initialize>) -> Result<()> {
     msg!("Starting initialization...");
     let config = &mut ctx.accounts.integration_config;
     config.authority = ctx.accounts.authority.key();
-    config.is_active = true;
+    config.is_active = false; // Activated by the protocol admin once a bond is posted
     config.bump = ctx.bumps.integration_config;
+    config.version = INTEGRATOR_VERSION;
     msg!("Config set. Authority: {}", config.authority);
 
     let pool_vault = &mut ctx.accounts.pool_vault;
     pool_vault.bump = ctx.bumps.pool_vault;
+    if pool_vault.version == 0 {
+        // Fresh pool: created directly on the current layout
+        pool_vault.version = POOL_VAULT_VERSION;
+        pool_vault.grace_period = DEFAULT_GRACE_PERIOD;
+        pool_vault.loss_index = YIELD_PRECISION;
+    }
+    if pool_vault.rate_model == RateModel::default() {
+        // Fresh pool: start on the default curve until the admin tunes it
+        pool_vault.rate_model = RateModel {
+            base_rate_bps: DEFAULT_BASE_RATE_BPS,
+            slope1_bps: DEFAULT_SLOPE1_BPS,
+            kink_bps: DEFAULT_KINK_BPS,
+            slope2_bps: DEFAULT_SLOPE2_BPS,
+        };
+    }
     msg!("Pool Vault set. Bump: {}", pool_vault.bump);
 
     msg!("Integrator Config Initialized Success");
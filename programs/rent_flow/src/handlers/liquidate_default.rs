@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use anchor_spl::token_2022::{self, Token2022, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::BookingObligation;
+use crate::state::{BookingObligation, HostProfile, IntegratorBond, IntegratorConfig, ObligationStatus, Property, ProtocolConfig, ReserveFund, SupportedToken};
+use crate::constants::{INTEGRATOR_VERSION, OBLIGATION_VERSION, PAUSE_LIQUIDATE, POOL_VAULT_VERSION, SUPPORTED_TOKEN_VERSION};
 use crate::error::ErrorCode;
+use crate::token_extensions::net_of_fee;
+use crate::math::bps_of;
 use crate::state::PoolVault;
 
 #[derive(Accounts)]
@@ -15,8 +18,10 @@ pub struct LiquidateDefault<'info> {
         mut,
         seeds = [b"obligation", nft_mint.key().as_ref()],
         bump = obligation.bump,
-        // RULE: Only liquidate if not already settled and past due
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        // RULE: Only liquidate a funded loan that is not already settled, and past due
         constraint = !obligation.is_settled @ ErrorCode::AlreadySettled,
+        constraint = obligation.has_active_loan() @ ErrorCode::LoanNotFunded,
     )]
     pub obligation: Account<'info, BookingObligation>,
 
@@ -39,8 +44,105 @@ pub struct LiquidateDefault<'info> {
     )]
     pub pool_nft_ata: InterfaceAccount<'info, TokenAccount>,
 
+    // Custodian of seized NFTs, whatever the loan currency
+    #[account(
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
     pub pool_vault: Account<'info, PoolVault>,
-    
+
+    // The lending pool of the loan's currency
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+
+    // --- Integrator Slashing Corridor ---
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_LIQUIDATE) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+
+    #[account(address = integrator_bond.mint @ ErrorCode::MintMismatch)]
+    pub bond_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = integrator_bond,
+        associated_token::mint = bond_mint,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub bond_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // A bond in another currency than the loan is slashed into the reserve instead
+    #[account(
+        mut,
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = bond_mint,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub reserve_bond_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Bounty Corridor (Pool -> Liquidator) ---
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::authority = liquidator,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub liquidator_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,      // For the loan currency
+    pub bond_token_program: Interface<'info, TokenInterface>, // For the bond currency
+
+    #[account(
+        mut,
+        seeds = [b"host_profile", obligation.host_wallet.as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
     pub token_2022_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -51,9 +153,10 @@ pub fn handler(ctx: Context<LiquidateDefault>) -> Result<()> {
     let clock = &ctx.accounts.clock;
     let obligation = &mut ctx.accounts.obligation;
 
-    // 1. THE GRACE PERIOD SENSOR (7 days expressed in seconds)
-    const GRACE_PERIOD: i64 = 60 * 60 * 24 * 7; 
-    let liquidation_threshold = obligation.end_date.checked_add(GRACE_PERIOD).unwrap();
+    // 1. THE GRACE PERIOD SENSOR (snapshotted on the obligation when it was funded)
+    let liquidation_threshold = obligation.end_date
+        .checked_add(obligation.grace_period)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     require!(
         clock.unix_timestamp > liquidation_threshold,
@@ -84,9 +187,115 @@ pub fn handler(ctx: Context<LiquidateDefault>) -> Result<()> {
         1, 0,
     )?;
 
-    // 3. EXIT: Mark as Default
+    // 3. THE SLASH: The vouching integrator's bond absorbs part of the loss
+    //    Same currency: into the loan's pool. Other currency: into the reserve fund as first-loss cover.
+    //    Either way it is refunded if the host redeems.
+    let bond_in_loan_currency = ctx.accounts.bond_mint.key() == ctx.accounts.loan_mint.key();
+    let debt = obligation.borrowed_amount;
+    let bond = &mut ctx.accounts.integrator_bond;
+    let slash_target = bps_of(debt, ctx.accounts.protocol_config.slash_bps as u64)?;
+    let slashable = bond.amount.checked_add(bond.pending_unbond).ok_or(ErrorCode::MathOverflow)?;
+    let slash = slash_target.min(slashable);
+
+    if slash > 0 {
+        let config_key = ctx.accounts.integration_config.key();
+        let bond_seeds: &[&[&[u8]]] = &[&[
+            b"integrator_bond",
+            config_key.as_ref(),
+            &[bond.bump],
+        ]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.bond_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.bond_token_ata.to_account_info(),
+                    mint: ctx.accounts.bond_mint.to_account_info(),
+                    to: if bond_in_loan_currency {
+                        ctx.accounts.token_vault.to_account_info()
+                    } else {
+                        ctx.accounts.reserve_bond_ata.to_account_info()
+                    },
+                    authority: bond.to_account_info(),
+                },
+                bond_seeds,
+            ),
+            slash,
+            ctx.accounts.bond_mint.decimals,
+        )?;
+
+        // Active bond is slashed first, then anything waiting to unbond
+        let from_active = slash.min(bond.amount);
+        bond.amount -= from_active;
+        bond.pending_unbond -= slash - from_active;
+        bond.total_slashed = bond.total_slashed.checked_add(slash).ok_or(ErrorCode::MathOverflow)?;
+
+        // Transfer-fee mints: the recipient books only what landed
+        let landed = net_of_fee(&ctx.accounts.bond_mint.to_account_info(), slash)?;
+        if bond_in_loan_currency {
+            obligation.bond_slashed = landed;
+        } else {
+            obligation.reserve_slashed = landed;
+            let reserve = &mut ctx.accounts.reserve_fund;
+            reserve.total_contributed = reserve.total_contributed
+                .checked_add(landed)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        msg!("Integrator Bond Slashed: {} ({} landed)", slash, landed);
+    }
+
+    // 4. THE BOUNTY: Paid now from idle pool cash, otherwise out of the auction proceeds
+    //    (never on a debt-free obligation: the pool would advance a claim nobody owes)
+    let bounty = if debt > 0 { ctx.accounts.protocol_config.liquidation_bounty.amount(debt)? } else { 0 };
+    let pool = &mut ctx.accounts.supported_token;
+    let pool_cash = ctx.accounts.token_vault.amount
+        .checked_add(obligation.bond_slashed).ok_or(ErrorCode::MathOverflow)?; // Includes the slash just received
+    if bounty > 0 && bounty <= pool.available_liquidity() && bounty <= pool_cash {
+        let loan_mint_key = ctx.accounts.loan_mint.key();
+        let pool_seeds: &[&[&[u8]]] = &[&[b"vault", loan_mint_key.as_ref(), &[pool.bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: ctx.accounts.liquidator_token_ata.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                pool_seeds,
+            ),
+            bounty,
+            ctx.accounts.loan_mint.decimals,
+        )?;
+
+        // The advance becomes part of the pool's claim on the seized booking
+        pool.outstanding_principal = pool.outstanding_principal
+            .checked_add(bounty)
+            .ok_or(ErrorCode::MathOverflow)?;
+        obligation.bounty_paid = bounty;
+    } else {
+        obligation.bounty_owed = bounty;
+    }
+    obligation.liquidator = ctx.accounts.liquidator.key();
+    msg!("Liquidation Bounty: {} (paid {}, owed {})", bounty, obligation.bounty_paid, obligation.bounty_owed);
+
+    // 5. REPUTATION: The host is blocked from borrowing until the default is cured
+    let profile = &mut ctx.accounts.host_profile;
+    profile.default_count = profile.default_count.saturating_add(1);
+    profile.active_defaults = profile.active_defaults.saturating_add(1);
+    if let Some(property) = Property::linked(obligation, &mut ctx.accounts.property)? {
+        property.record_default(clock.unix_timestamp);
+    }
+
+    // 6. EXIT: Mark as Default
     obligation.is_settled = true; // Prevents future settlement attempts
-    // obligation.status = Status::Defaulted; 
+    obligation.status = ObligationStatus::Defaulted;
+    obligation.default_cured = false;
+    obligation.defaulted_at = clock.unix_timestamp;
+    // The host's last chance to redeem; bidding and the auction clock start after it
+    obligation.redeem_deadline = clock.unix_timestamp
+        .checked_add(ctx.accounts.protocol_config.redemption.window)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     msg!("Liquidation Complete: Asset Seized for Investors.");
     Ok(())
@@ -1,86 +1,356 @@
 /*
+  Liquidated Asset Auction 🧱
 
-### 🧱 Future Implementation: `liquidated_asset_auction.rs`
+  The Problem: After `liquidate_default` the PoolVault is "Asset Heavy" (owns the
+  booking NFT) but "Cash Poor" (lent cash gone). Investors cannot withdraw an NFT fragment.
 
-**The Problem:** The `PoolVault` is now "Asset Heavy" (owns NFTs) but "Cash Poor" (lost USDC). Investors cannot withdraw an NFT fragment.
-**The Solution:** An internal Dutch Auction or a "Buy-Now" trigger where any liquidator can purchase the NFT for USDC, which is then re-injected into the `PoolVault`.
+  The Solution: A Dutch auction. Any buyer can purchase the seized NFT in the loan's currency.
+  Bidding opens once the host's redemption window (see redeem_defaulted) closes.
+  From then the price starts at the outstanding debt and decays linearly to a floor
+  over AUCTION_DECAY_PERIOD, so LPs are protected from "vulture liquidators" early on.
 
-#### 1. The Computer Science Rule: Price Discovery Invariants
+  The claim being auctioned is the debt plus the liquidation bounty: an advance
+  the pool paid at seizure, or a bounty still owed to the liquidator, which is
+  paid out of these proceeds first.
 
-On-chain assets without an active oracle price require a **Price Discovery Mechanism**.
+  Whatever the sale and the integrator's slashed bond do not recover is the
+  shortfall. The reserve fund covers it first; only what the reserve cannot pay
+  is a loss for the LPs. Whatever they recover above the claim is LP yield.
 
-* **The Rule:** The protocol must ensure the NFT isn't sold for less than the `disbursed_amount + penalties` unless a specific time-decay (Dutch Auction) has occurred. This protects the LPs from "vulture liquidators" buying the RWA for 1 penny.
+  A written-off obligation stays on sale: its loss is already on the books, so
+  the proceeds (after any bounty still owed) are a recovery credited to the LPs.
+*/
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{self, Token2022},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+use crate::constants::{AUCTION_DECAY_PERIOD, AUCTION_FLOOR_BPS, INTEGRATOR_VERSION, OBLIGATION_VERSION, PAUSE_LIQUIDATE, POOL_VAULT_VERSION, SUPPORTED_TOKEN_VERSION};
+use crate::state::{BookingObligation, HostProfile, IntegratorConfig, ObligationStatus, PoolVault, Property, ProtocolConfig, ReserveFund, SupportedToken};
+use crate::error::ErrorCode;
+use crate::token_extensions::{gross_up, net_of_fee};
+use crate::events::LossRealized;
+use crate::math::bps_of;
 
-#### 2. Trace the Data Flow: The Liquidation Exit
+#[derive(Accounts)]
+pub struct PurchaseSeizedCollateral<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
 
-1. **Entry:** A Buyer calls `purchase_seized_collateral` providing USDC.
-2. **Transformation (Math):** * Calculate `MinPrice = Obligation.Principal + ProtocolFees`.
-* Verify `Buyer.USDC >= MinPrice`.
+    #[account(
+        mut,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = matches!(obligation.status, ObligationStatus::Defaulted | ObligationStatus::WrittenOff) @ ErrorCode::NotDefaulted,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
 
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
 
-3. **Movement:**
-* USDC: `Buyer`  `PoolVault`.
-* NFT: `PoolVault`  `Buyer`.
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_LIQUIDATE) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
+    // Custodian of seized NFTs, whatever the loan currency
+    #[account(
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
 
-4. **Exit:** `PoolVault.total_liquidity_tracked` increases; NFT is removed from protocol state.
+    // The lending pool of the loan's currency
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
 
----
+    #[account(
+        mut,
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
 
-### 3. Future Pseudo-Code Logic
+    #[account(
+        mut,
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
 
-```rust
-/* FILE: src/future/claim_collateral.rs
-    STATUS: PROPOSAL (Post-MVP)
-*/
+    #[account(
+        mut,
+        seeds = [b"host_profile", obligation.host_wallet.as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
 
-pub fn buy_seized_nft(ctx: Context<BuySeizedNFT>) -> Result<()> {
-    // 1. DATA ENTRY: Get the debt data from the defaulted obligation
-    let obligation = &ctx.accounts.obligation;
-    let principal = obligation.booking_value;
-    
-    // 2. THE PRICE INVARIANT: Ensure investors are made whole
-    // Future Logic: Add a 'Time Decay' factor if the NFT doesn't sell for 30 days
-    let floor_price = principal.checked_add(FIXED_LIQUIDATION_FEE)?;
-    
-    // 3. THE SWAP (USDC for NFT)
-    // Transformation: Move USDC from Buyer to the Investor Pool
-    token_interface::transfer_checked(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked { /* Buyer -> PoolVault */ }
-        ),
-        floor_price,
-        6 // USDC Decimals
-    )?;
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
+
+    // --- Asset Corridor (Pool -> Buyer) ---
+    #[account(
+        mut,
+        associated_token::authority = pool_vault,
+        associated_token::mint = nft_mint,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub pool_nft_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::authority = buyer,
+        associated_token::mint = nft_mint,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub buyer_nft_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Cash Corridor (Buyer / Reserve -> Pool) ---
+    #[account(mut)]
+    pub buyer_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Bounty Corridor (Buyer -> Liquidator, deferred bounties only) ---
+    /// CHECK: Only receives the deferred bounty
+    #[account(address = obligation.liquidator)]
+    pub liquidator: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::authority = liquidator,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub liquidator_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,   // For the loan currency
+    pub token_2022_program: Program<'info, Token2022>,     // For NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Current Dutch auction price: full debt when bidding opens, decaying linearly to the floor.
+pub fn auction_price(debt: u64, opens_at: i64, now: i64) -> Result<u64> {
+    let floor = bps_of(debt, AUCTION_FLOOR_BPS)?;
+    let elapsed = now
+        .checked_sub(opens_at).ok_or(ErrorCode::MathOverflow)?
+        .clamp(0, AUCTION_DECAY_PERIOD) as u128;
+
+    let decay = ((debt - floor) as u128)
+        .checked_mul(elapsed).ok_or(ErrorCode::MathOverflow)?
+        .checked_div(AUCTION_DECAY_PERIOD as u128).ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(debt - decay as u64)
+}
 
-    // Transformation: Move the 'Sportscar' NFT to the Buyer
-    let signer_seeds = &[ /* PoolVault Seeds */ ];
+pub fn handler(ctx: Context<PurchaseSeizedCollateral>, max_price: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let debt = ctx.accounts.obligation.borrowed_amount;
+    let bounty_paid = ctx.accounts.obligation.bounty_paid;
+    let bounty_owed = ctx.accounts.obligation.bounty_owed;
+    let decimals = ctx.accounts.loan_mint.decimals;
+    require!(now > ctx.accounts.obligation.redeem_deadline, ErrorCode::RedemptionWindowOpen);
+
+    // 1. PRICE DISCOVERY: The pool's claim (debt + advanced bounty) plus any bounty still owed
+    let pool_claim = debt.checked_add(bounty_paid).ok_or(ErrorCode::MathOverflow)?;
+    let claim = pool_claim.checked_add(bounty_owed).ok_or(ErrorCode::MathOverflow)?;
+    // The clock starts when the redemption window closes, not at seizure
+    let price = auction_price(claim, ctx.accounts.obligation.redeem_deadline, now)?;
+
+    // 2. THE SWAP: Loan currency (Buyer -> Liquidator first, then Pool)
+    //    Transfer-fee mints: the buyer covers the fee so each leg lands in full,
+    //    and `max_price` bounds what actually leaves the buyer's account
+    let to_liquidator = price.min(bounty_owed);
+    let to_pool = price - to_liquidator;
+    let loan_mint = ctx.accounts.loan_mint.to_account_info();
+    let legs = [
+        (ctx.accounts.liquidator_token_ata.to_account_info(), gross_up(&loan_mint, to_liquidator)?),
+        (ctx.accounts.token_vault.to_account_info(), gross_up(&loan_mint, to_pool)?),
+    ];
+    let cost = legs[0].1.checked_add(legs[1].1).ok_or(ErrorCode::MathOverflow)?;
+    require!(cost <= max_price, ErrorCode::AuctionPriceTooHigh);
+    for (destination, amount) in legs {
+        if amount == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.buyer_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            amount,
+            decimals,
+        )?;
+    }
+
+    // 3. THE SWAP: NFT (Pool -> Buyer)
+    let pool_seeds: &[&[&[u8]]] = &[&[b"pool_vault", &[ctx.accounts.pool_vault.bump]]];
     token_2022::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_2022_program.to_account_info(),
-            TransferChecked { /* PoolVault -> Buyer */ },
-            signer_seeds
+            token_2022::TransferChecked {
+                from: ctx.accounts.pool_nft_ata.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.buyer_nft_ata.to_account_info(),
+                authority: ctx.accounts.pool_vault.to_account_info(),
+            },
+            pool_seeds,
         ),
         1, 0
     )?;
 
-    // 4. EXIT: Re-mark the obligation as 'RECOVERED'
+    // WRITTEN OFF: Claim, cover and exposure were all resolved at write-off; only the recovery is left
+    if ctx.accounts.obligation.status == ObligationStatus::WrittenOff {
+        ctx.accounts.supported_token.distribute_yield(to_pool)?;
+
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.recovered_amount = to_pool;
+        obligation.bounty_owed = bounty_owed - to_liquidator;
+        obligation.status = ObligationStatus::Recovered;
+
+        msg!("Written-Off Booking Sold: {}, LP Recovery: {}", price, to_pool);
+        return Ok(());
+    }
+
+    // 4. FIRST-LOSS COVER: The reserve pays the shortfall before LPs do
+    let recovered = to_pool
+        .checked_add(ctx.accounts.obligation.bond_slashed)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let shortfall = pool_claim.saturating_sub(recovered);
+    let surplus = recovered.saturating_sub(pool_claim);
+    let cover = shortfall.min(ctx.accounts.reserve_token_ata.amount);
+    if cover > 0 {
+        let reserve_seeds: &[&[&[u8]]] = &[&[b"reserve_fund", &[ctx.accounts.reserve_fund.bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.reserve_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.reserve_fund.to_account_info(),
+                },
+                reserve_seeds,
+            ),
+            cover,
+            decimals,
+        )?;
+
+        let reserve = &mut ctx.accounts.reserve_fund;
+        reserve.total_covered = reserve.total_covered
+            .checked_add(cover)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    // Only what lands in the vault reduces the LPs' loss
+    let cover = net_of_fee(&loan_mint, cover)?;
+
+    // 5. EXIT: The loan leaves the pool's books; what nobody covered is written off LP principal
+    let lp_loss = shortfall - cover;
+    let pool = &mut ctx.accounts.supported_token;
+    pool.outstanding_principal = pool.outstanding_principal
+        .checked_sub(pool_claim)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.realize_loss(lp_loss)?;
+    // Proceeds and slash above the claim stay in the vault: the LPs carried the risk, they earn it
+    if surplus > 0 {
+        pool.distribute_yield(surplus)?;
+    }
+
+    let integrator = &mut ctx.accounts.integration_config;
+    integrator.outstanding_principal = integrator.outstanding_principal
+        .checked_sub(debt)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let profile = &mut ctx.accounts.host_profile;
+    profile.outstanding_principal = profile.outstanding_principal
+        .checked_sub(debt)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if let Some(property) = Property::linked(&ctx.accounts.obligation, &mut ctx.accounts.property)? {
+        property.record_resolution(debt, shortfall)?;
+    }
+
+    let obligation = &mut ctx.accounts.obligation;
+    obligation.recovered_amount = to_pool;
+    obligation.bounty_owed = bounty_owed - to_liquidator;
+    obligation.reserve_covered = cover;
+    obligation.status = ObligationStatus::Recovered;
+
+    msg!(
+        "Seized Booking Sold: {}, Reserve Cover: {}, LP Loss: {}, LP Surplus: {}",
+        price, cover, lp_loss, surplus
+    );
+
+    if lp_loss > 0 {
+        emit!(LossRealized {
+            obligation: ctx.accounts.obligation.key(),
+            claim: pool_claim,
+            recovered: to_pool,
+            bond_slashed: ctx.accounts.obligation.bond_slashed,
+            reserve_covered: cover,
+            lp_loss,
+        });
+    }
     Ok(())
 }
 
-```
+#[cfg(test)]
+mod tests {
+    use super::*;
 
----
+    const OPENS_AT: i64 = 1_000_000;
 
-### 🏁 MVP Summary & Next Step
+    #[test]
+    fn price_holds_at_full_claim_until_bidding_opens() {
+        assert_eq!(auction_price(10_000, OPENS_AT, OPENS_AT - 86_400).unwrap(), 10_000);
+        assert_eq!(auction_price(10_000, OPENS_AT, OPENS_AT).unwrap(), 10_000);
+    }
 
-You have successfully built the **Core Lifecycle** of an RWA Protocol:
+    #[test]
+    fn price_decays_linearly_to_the_floor() {
+        let halfway = OPENS_AT + AUCTION_DECAY_PERIOD / 2;
+        assert_eq!(auction_price(10_000, OPENS_AT, halfway).unwrap(), 7_500);
+        assert_eq!(auction_price(10_000, OPENS_AT, OPENS_AT + AUCTION_DECAY_PERIOD).unwrap(), 5_000);
+        assert_eq!(auction_price(10_000, OPENS_AT, OPENS_AT + 10 * AUCTION_DECAY_PERIOD).unwrap(), 5_000);
+    }
 
-1. **Minting:** Tokenizing the Airbnb Booking.
-2. **Deposit:** Atomic Swap (NFT In / USDC Out).
-3. **Settlement:** Repayment Transformation (USDC + Yield In / NFT Out).
-4. **Liquidation:** The Default Safety Sensor (NFT Seizure).
-
-**Your Chassis is complete.** **Next Step:** Since we have the Rust logic stable, would you like me to show you how to write the **TypeScript Test Case** for `liquidate_default` to ensure your "Grace Period" logic correctly blocks early liquidations?
-*/
\ No newline at end of file
+    #[test]
+    fn empty_claim_is_free() {
+        assert_eq!(auction_price(0, OPENS_AT, OPENS_AT + 1).unwrap(), 0);
+    }
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::constants::OBLIGATION_VERSION;
 use crate::state::BookingObligation;
 use crate::error::ErrorCode;
 
@@ -14,7 +15,8 @@ pub struct LockCycle<'info> {
     #[account(
         mut,
        seeds = [b"obligation", nft_mint.key().as_ref()],
-       bump = booking_obligation.bump, 
+       bump = booking_obligation.bump,
+       constraint = booking_obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
         // Rule: Ensure the host signing IS the host stored in the data
         constraint = booking_obligation.host_wallet == host.key() @ ErrorCode::NotHubOwner
     )]
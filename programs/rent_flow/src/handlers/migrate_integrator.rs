@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::migration::migrate;
+use crate::state::IntegratorConfig;
+
+#[derive(Accounts)]
+pub struct MigrateIntegrator<'info> {
+    // Permissionless: anyone can pay the rent delta to move an account forward
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Cannot be loaded as Account<IntegratorConfig> until migrated; owner, seeds and
+    /// discriminator are verified here and in `migrate`.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump
+    )]
+    pub integration_config: UncheckedAccount<'info>,
+
+    /// CHECK: Used only for seed derivation
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateIntegrator>) -> Result<()> {
+    let migrated = migrate::<IntegratorConfig>(
+        &ctx.accounts.integration_config.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + IntegratorConfig::INIT_SPACE,
+    )?;
+
+    msg!("Integrator Config Migrated to v{}", migrated.version);
+    Ok(())
+}
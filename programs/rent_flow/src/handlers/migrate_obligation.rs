@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::constants::POOL_VAULT_VERSION;
+use crate::error::ErrorCode;
+use crate::migration::migrate_with;
+use crate::state::{BookingObligation, PoolVault};
+
+#[derive(Accounts)]
+pub struct MigrateObligation<'info> {
+    // Permissionless: anyone can pay the rent delta to move an account forward
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Cannot be loaded as Account<BookingObligation> until migrated; owner, seeds and
+    /// discriminator are verified here and in `migrate`.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump
+    )]
+    pub obligation: UncheckedAccount<'info>,
+
+    /// CHECK: Used only for seed derivation
+    pub nft_mint: UncheckedAccount<'info>,
+
+    // Loans funded before multi-currency lending were lent in the pool's legacy currency
+    #[account(
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = pool_vault.base_mint != Pubkey::default() @ ErrorCode::AccountNotMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateObligation>) -> Result<()> {
+    let base_mint = ctx.accounts.pool_vault.base_mint;
+    let migrated = migrate_with::<BookingObligation>(
+        &ctx.accounts.obligation.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + BookingObligation::INIT_SPACE,
+        |obligation| {
+            if obligation.loan_mint == Pubkey::default() {
+                obligation.loan_mint = base_mint;
+            }
+            Ok(())
+        },
+    )?;
+
+    msg!("Booking Obligation Migrated to v{}", migrated.version);
+    Ok(())
+}
@@ -0,0 +1,117 @@
+/*
+  Pool Liquidity Migration 🚚
+
+  Before multi-currency lending the PoolVault held every book and its own USDC
+  ATA held the cash. Lending books now live on each SupportedToken, with the
+  cash in that token's vault.
+
+  This one-shot admin move carries the legacy pool over to the SupportedToken
+  of its currency: the cash, the books, and the yield / loss indices (copied
+  verbatim, so unharvested LP positions stay consistent). The PoolVault keeps
+  the rate model, grace periods and custody of seized NFTs, and remembers the
+  legacy currency in `base_mint` for migrate_position / migrate_obligation.
+*/
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::constants::{POOL_VAULT_VERSION, SUPPORTED_TOKEN_VERSION};
+use crate::state::{PoolVault, ProtocolConfig, SupportedToken};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct MigratePoolLiquidity<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = pool_vault.base_mint == Pubkey::default() @ ErrorCode::AlreadyMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // Legacy cash box
+    #[account(
+        mut,
+        associated_token::authority = pool_vault,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<MigratePoolLiquidity>) -> Result<()> {
+    // 🚩 A currency that already has LPs of its own can't absorb the legacy books
+    require!(ctx.accounts.supported_token.total_deposits == 0, ErrorCode::AlreadyMigrated);
+
+    // 1. CASH: Legacy pool ATA -> the currency's vault
+    let cash = ctx.accounts.pool_token_ata.amount;
+    if cash > 0 {
+        let pool_seeds: &[&[&[u8]]] = &[&[b"pool_vault", &[ctx.accounts.pool_vault.bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.pool_token_ata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.pool_vault.to_account_info(),
+                },
+                pool_seeds,
+            ),
+            cash,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    // 2. BOOKS: Copied as-is, then cleared on the PoolVault
+    let legacy = &mut ctx.accounts.pool_vault;
+    let pool = &mut ctx.accounts.supported_token;
+    pool.total_liquidity_tracked = pool.total_liquidity_tracked
+        .checked_add(legacy.total_liquidity_tracked)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.outstanding_principal = legacy.outstanding_principal;
+    pool.total_deposits = legacy.total_deposits;
+    pool.total_yield_earned = legacy.total_yield_earned;
+    pool.acc_yield_per_share = legacy.acc_yield_per_share;
+    pool.loss_index = legacy.loss_index;
+    pool.total_losses = legacy.total_losses;
+
+    legacy.total_liquidity_tracked = 0;
+    legacy.outstanding_principal = 0;
+    legacy.total_deposits = 0;
+    legacy.total_yield_earned = 0;
+    legacy.acc_yield_per_share = 0;
+    legacy.total_losses = 0;
+    legacy.base_mint = pool.mint;
+
+    msg!("Pool Liquidity Migrated: {} moved to the {} vault", cash, pool.mint);
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::migration::migrate;
+use crate::state::PoolVault;
+
+#[derive(Accounts)]
+pub struct MigratePoolVault<'info> {
+    // Permissionless: anyone can pay the rent delta to move an account forward
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Cannot be loaded as Account<PoolVault> until migrated; owner, seeds and
+    /// discriminator are verified here and in `migrate`.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"pool_vault"],
+        bump
+    )]
+    pub pool_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigratePoolVault>) -> Result<()> {
+    let migrated = migrate::<PoolVault>(
+        &ctx.accounts.pool_vault.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + PoolVault::INIT_SPACE,
+    )?;
+
+    msg!("Pool Vault Migrated to v{}", migrated.version);
+    Ok(())
+}
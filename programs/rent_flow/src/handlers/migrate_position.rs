@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::constants::POOL_VAULT_VERSION;
+use crate::error::ErrorCode;
+use crate::migration::decode_stored;
+use crate::state::{PoolVault, Position, Versioned};
+
+/// Positions are per currency now (["position", owner, mint]); this moves a
+/// single-pool position (["position", owner]) to the pool's legacy currency,
+/// upgrading its layout on the way, and closes the old account.
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    // Permissionless: anyone can pay the rent to move an account forward
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Cannot be loaded as Account<Position> until migrated; owner, seeds and
+    /// discriminator are verified here and in `decode_stored`.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"position", owner.key().as_ref()],
+        bump
+    )]
+    pub legacy_position: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", owner.key().as_ref(), pool_vault.base_mint.as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    // Legacy books must have moved first (migrate_pool_liquidity)
+    #[account(
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = pool_vault.base_mint != Pubkey::default() @ ErrorCode::AccountNotMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+
+    /// CHECK: Used for seed derivation; receives the legacy account's rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigratePosition>) -> Result<()> {
+    // 1. DECODE: Legacy bytes in the current layout
+    let legacy = ctx.accounts.legacy_position.to_account_info();
+    let mut migrated = decode_stored::<Position>(&legacy, 8 + Position::INIT_SPACE)?;
+    if migrated.version() < Position::VERSION {
+        migrated.upgrade();
+    }
+    migrated.bump = ctx.bumps.position;
+
+    // 2. RELOCATE: Same position under the per-currency seeds
+    ctx.accounts.position.set_inner(migrated);
+
+    // 3. CLOSE: The legacy account's rent goes back to its owner
+    let owner = ctx.accounts.owner.to_account_info();
+    let refund = legacy.lamports();
+    **owner.try_borrow_mut_lamports()? = owner.lamports()
+        .checked_add(refund)
+        .ok_or(ErrorCode::MathOverflow)?;
+    **legacy.try_borrow_mut_lamports()? = 0;
+    legacy.assign(&anchor_lang::system_program::ID);
+    legacy.resize(0)?;
+
+    msg!("Position Migrated to v{} in {}", ctx.accounts.position.version, ctx.accounts.pool_vault.base_mint);
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::migration::migrate_with;
+use crate::state::{ProtocolConfig, ReserveFund, SupportedToken};
+use crate::error::ErrorCode;
+use crate::token_extensions::net_of_fee;
+
+#[derive(Accounts)]
+pub struct MigrateSupportedToken<'info> {
+    // Permissionless: anyone can pay the rent delta to move an account forward
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Same size as before versioning (the version byte came out of `_reserved`),
+    /// so it is loaded raw like the others; owner, seeds and discriminator are verified.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump
+    )]
+    pub supported_token: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // v2 lends from the token's vault ATA; older listings may not have one yet,
+    // nor treasury / reserve cash boxes for the currency
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = supported_token,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // Pre-v2 listings created a keypair vault instead; anything in it is swept into the ATA
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = supported_token,
+        token::token_program = token_program,
+        constraint = legacy_token_vault.key() != token_vault.key() @ ErrorCode::AlreadyMigrated,
+    )]
+    pub legacy_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = protocol_config,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = reserve_fund,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateSupportedToken>) -> Result<()> {
+    // 1. SWEEP: Legacy keypair vault -> the token's vault ATA
+    let swept = match &ctx.accounts.legacy_token_vault {
+        Some(legacy) if legacy.amount > 0 => {
+            let mint_key = ctx.accounts.mint.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"vault", mint_key.as_ref(), &[ctx.bumps.supported_token]]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: legacy.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.token_vault.to_account_info(),
+                        authority: ctx.accounts.supported_token.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                legacy.amount,
+                ctx.accounts.mint.decimals,
+            )?;
+            // Transfer-fee mints withhold part of it in the vault; only what lands is booked
+            net_of_fee(&ctx.accounts.mint.to_account_info(), legacy.amount)?
+        }
+        _ => 0,
+    };
+
+    // 2. MIGRATE: Nothing ever lent from the keypair vault, so its cash is unclaimed: it goes to the LPs
+    let migrated = migrate_with::<SupportedToken>(
+        &ctx.accounts.supported_token.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        SupportedToken::SIZE,
+        |pool| pool.distribute_yield(swept),
+    )?;
+
+    msg!("Supported Token Migrated to v{}, {} swept from the legacy vault", migrated.version, swept);
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
-    program::invoke,
+    program::{invoke, invoke_signed},
     system_instruction,
     sysvar::instructions as instructions_sysvar,
 };
@@ -8,12 +8,14 @@ use anchor_spl::{
     associated_token::{AssociatedToken, spl_associated_token_account},
     token_2022::{self, Token2022, MintTo},
 };
-use crate::state::{IntegratorConfig, BookingObligation, ProfitTier};
+use crate::state::{IntegratorConfig, IntegratorBond, BookingObligation, NonceBitmap, ProfitTier, Property, ProtocolConfig};
+use crate::constants::{INTEGRATOR_VERSION, MAX_BOOKING_ID_LEN, MAX_CLOCK_SKEW, MAX_PROOF_VALIDITY, NONCES_PER_PAGE, OBLIGATION_VERSION, PAUSE_MINT};
+use crate::ed25519::verify_preceding_signature;
 use crate::error::ErrorCode;
-
-pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+use crate::proof::{BookingProof, BookingProofEnvelope};
 
 #[derive(Accounts)]
+#[instruction(booking_data: BookingProofEnvelope)]
 pub struct MintBooking<'info> {
     #[account(mut)]
     pub host: Signer<'info>,
@@ -21,6 +23,7 @@ pub struct MintBooking<'info> {
     #[account(
         seeds = [b"integrator", integration_wallet.key().as_ref()],
         bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
         constraint = integration_config.is_active @ ErrorCode::IntegratorNotAuthorized
     )]
     pub integration_config: Account<'info, IntegratorConfig>,
@@ -28,6 +31,19 @@ pub struct MintBooking<'info> {
     /// CHECK: Seed derivation only
     pub integration_wallet: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_MINT) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: Manually created via System Program to support Token-2022 Extensions
     #[account(mut, signer)]
     pub nft_mint: AccountInfo<'info>,
@@ -45,6 +61,24 @@ pub struct MintBooking<'info> {
     )]
     pub booking_obligation: Account<'info, BookingObligation>,
 
+    // --- Replay Guard: the page of the oracle's nonce bitmap holding this proof's nonce ---
+    #[account(
+        init_if_needed,
+        payer = host,
+        space = 8 + NonceBitmap::INIT_SPACE,
+        seeds = [
+            b"nonce_page",
+            protocol_config.oracle.as_ref(),
+            &(booking_data.proof.nonce / NONCES_PER_PAGE).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub nonce_page: Account<'info, NonceBitmap>,
+
+    // --- Property: the listing this booking is for (required if the proof names one) ---
+    #[account(mut)]
+    pub property: Option<Account<'info, Property>>,
+
     /// CHECK: Instruction Introspection Sysvar
     #[account(address = instructions_sysvar::ID)]
     pub sysvar_instructions: AccountInfo<'info>,
@@ -55,105 +89,229 @@ pub struct MintBooking<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct BookingProof {
-    pub booking_id: String,
-    pub amount: u64,
-    pub start_date: i64,
-    pub end_date: i64,
-    pub host_wallet: Pubkey,
-    pub oracle_pubkey: Pubkey,
-    pub tier_index: u8,
-    pub investor_wallet: Pubkey,
+impl BookingProof {
+    /// Freshness and shape checks on an attested booking; returns its tier.
+    pub fn validate(&self, now: i64) -> Result<ProfitTier> {
+        self.validate_within(now, MAX_PROOF_VALIDITY)
+    }
+
+    /// `validate`, with proofs allowed to stay valid for up to `max_validity` seconds.
+    pub fn validate_within(&self, now: i64, max_validity: i64) -> Result<ProfitTier> {
+        require!(
+            self.issued_at <= now.saturating_add(MAX_CLOCK_SKEW)
+                && now <= self.expires_at
+                && self.expires_at.saturating_sub(self.issued_at) <= max_validity,
+            ErrorCode::ProofNotFresh
+        );
+        require!(self.amount > 0, ErrorCode::InvalidAmount);
+        require!(self.booking_id.len() <= MAX_BOOKING_ID_LEN, ErrorCode::BookingIdTooLong);
+        require!(
+            self.start_date < self.end_date && self.end_date > now,
+            ErrorCode::InvalidBookingDates
+        );
+
+        let tier = ProfitTier::from_index(self.tier_index)?;
+        require!(
+            ProfitTier::for_duration(self.end_date - self.start_date) == Some(tier),
+            ErrorCode::TierDurationMismatch
+        );
+        Ok(tier)
+    }
 }
 
-pub fn handler(ctx: Context<MintBooking>, booking_data: BookingProof) -> Result<()> {
+/// A booking whose proof passed every check but the signature, which each
+/// mint path verifies its own way (one proof, a batch, or a Merkle root).
+pub struct AttestedBooking {
+    pub proof: BookingProof,
+    pub tier: ProfitTier,
+    pub booking_currency: [u8; 3],   // Zeroed = priced in the loan currency
+    pub listing_id: Option<Vec<u8>>, // Property the oracle attested, if any
+}
+
+impl AttestedBooking {
+    /// Checks `property` against this booking (integrator, host and, when the proof
+    /// attests one, the listing id) and counts the booking on it; returns its key.
+    pub fn link_property(&self, integrator_config: &Pubkey, property: Option<&mut Account<Property>>) -> Result<Pubkey> {
+        let Some(property) = property else {
+            require!(self.listing_id.is_none(), ErrorCode::MissingProperty);
+            return Ok(Pubkey::default());
+        };
+        require!(
+            property.integrator_config == *integrator_config
+                && property.host == self.proof.host_wallet
+                && self.listing_id.as_deref().is_none_or(|id| id == property.listing_id.as_bytes()),
+            ErrorCode::PropertyMismatch
+        );
+        property.total_bookings = property.total_bookings.saturating_add(1);
+        Ok(property.key())
+    }
+}
+
+impl BookingProofEnvelope {
+    /// Extension, oracle, host and freshness checks shared by every mint path.
+    pub fn attest(&self, oracle: &Pubkey, host: &Pubkey, now: i64) -> Result<AttestedBooking> {
+        self.attest_within(oracle, host, now, MAX_PROOF_VALIDITY)
+    }
+
+    /// `attest` for proofs that may stay valid for up to `max_validity` seconds
+    /// (leaves of a Merkle root live as long as the root).
+    pub fn attest_within(&self, oracle: &Pubkey, host: &Pubkey, now: i64, max_validity: i64) -> Result<AttestedBooking> {
+        self.extensions()?; // Rejects malformed or unknown critical extensions
+        let booking_currency = self.booking_currency()?.unwrap_or_default();
+        let listing_id = self.property_listing_id()?.map(<[u8]>::to_vec);
+        require_keys_eq!(self.proof.oracle_pubkey, *oracle, ErrorCode::InvalidOracleKey);
+        require_keys_eq!(self.proof.host_wallet, *host, ErrorCode::NotHubOwner);
+        let tier = self.proof.validate_within(now, max_validity)?;
+        Ok(AttestedBooking { proof: self.proof.clone(), tier, booking_currency, listing_id })
+    }
+}
+
+/// Everything needed to create a booking NFT and hand its single unit to the host.
+pub struct BookingNftAccounts<'a, 'info> {
+    pub host: &'a AccountInfo<'info>, // Pays for the mint and the ATA
+    pub nft_mint: &'a AccountInfo<'info>,
+    pub host_ata: &'a AccountInfo<'info>,
+    pub integration_config: &'a Account<'info, IntegratorConfig>,
+    pub integration_wallet: &'a AccountInfo<'info>,
+    pub token_2022_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+}
+
+impl BookingNftAccounts<'_, '_> {
+    /// Creates the mint (a signer, or a PDA signed for by `mint_seeds`), the host's ATA,
+    /// and mints the NFT with the integrator as mint authority.
+    pub fn mint_to_host(&self, mint_seeds: &[&[&[u8]]]) -> Result<()> {
+        let mint_key = self.nft_mint.key();
+        let space = 82; // Standard Token Mint space
+        let lamports = Rent::get()?.minimum_balance(space);
+
+        // Create Account
+        invoke_signed(
+            &system_instruction::create_account(
+                self.host.key, &mint_key, lamports, space as u64, self.token_2022_program.key,
+            ),
+            &[self.host.clone(), self.nft_mint.clone(), self.system_program.clone()],
+            mint_seeds,
+        )?;
+
+        // Initialize Transfer Hook - DISABLED to fix "Missing Account" error
+        /*
+        let ix_init_hook = spl_token_2022::extension::transfer_hook::instruction::initialize(
+            self.token_2022_program.key,
+            &mint_key,
+            Some(self.integration_config.key()),
+            Some(crate::ID),
+        )?;
+        invoke(&ix_init_hook, &[self.nft_mint.clone()])?;
+        */
+
+        // Initialize Mint
+        token_2022::initialize_mint(
+            CpiContext::new(self.token_2022_program.clone(), token_2022::InitializeMint {
+                mint: self.nft_mint.clone(),
+                rent: self.rent.clone(),
+            }),
+            0, &self.integration_config.key(), None,
+        )?;
+
+        // Create ATA
+        invoke(
+            &spl_associated_token_account::instruction::create_associated_token_account(
+                self.host.key, self.host.key, &mint_key, self.token_2022_program.key,
+            ),
+            &[
+                self.host.clone(), self.host_ata.clone(), self.nft_mint.clone(),
+                self.system_program.clone(), self.associated_token_program.clone(), self.token_2022_program.clone()
+            ],
+        )?;
+
+        // Mint the single unit, signed by the integrator PDA
+        let seeds: &[&[u8]] = &[b"integrator", self.integration_wallet.key.as_ref(), &[self.integration_config.bump]];
+        token_2022::mint_to(
+            CpiContext::new_with_signer(self.token_2022_program.clone(), MintTo {
+                mint: self.nft_mint.clone(),
+                to: self.host_ata.clone(),
+                authority: self.integration_config.to_account_info(),
+            }, &[seeds]),
+            1
+        )
+    }
+}
+
+impl BookingObligation {
+    /// Fills a freshly created obligation from its attested booking.
+    pub fn record_booking(&mut self, booking: AttestedBooking, integrator_wallet: Pubkey, nft_mint: Pubkey, bump: u8) {
+        self.tier = booking.tier;
+        self.booking_id = booking.proof.booking_id;
+        self.booking_value = booking.proof.amount;
+        self.booking_currency = booking.booking_currency;
+        self.start_date = booking.proof.start_date;
+        self.end_date = booking.proof.end_date;
+        self.integrator_wallet = integrator_wallet; // Links the loan to the vouching integrator
+        self.host_wallet = booking.proof.host_wallet;
+        self.nft_mint = nft_mint;
+        self.bump = bump;
+        self.version = OBLIGATION_VERSION;
+    }
+}
+
+pub fn handler(ctx: Context<MintBooking>, booking_data: BookingProofEnvelope) -> Result<()> {
     // --------------------------------------------
-    // PHASE 1: ORACLE VERIFICATION
+    // PHASE 0: INTEGRATOR SKIN IN THE GAME
     // --------------------------------------------
-    let ixs = ctx.accounts.sysvar_instructions.to_account_info();
-    let current_index = instructions_sysvar::load_current_index_checked(&ixs)?;
-    let prev_index = current_index.checked_sub(1).ok_or(ErrorCode::InvalidInstructionIndex)?;
-    let ed25519_ix = instructions_sysvar::load_instruction_at_checked(prev_index as usize, &ixs)?;
-    
-    require_keys_eq!(ed25519_ix.program_id, ED25519_PROGRAM_ID, ErrorCode::InvalidProgramId);
+    let required_bond = ctx.accounts.protocol_config
+        .required_bond(ctx.accounts.integration_config.outstanding_principal)?;
+    require!(
+        ctx.accounts.integrator_bond.amount >= required_bond,
+        ErrorCode::InsufficientIntegratorBond
+    );
 
     // --------------------------------------------
-    // PHASE 2: MANUAL TOKEN-2022 SETUP
+    // PHASE 1: ORACLE VERIFICATION
     // --------------------------------------------
-    let mint_key = ctx.accounts.nft_mint.key();
-    let space = 82; // Standard Token Mint space
-    let lamports = Rent::get()?.minimum_balance(space);
-
-    // Create Account
-    invoke(
-        &system_instruction::create_account(
-            ctx.accounts.host.key, &mint_key, lamports, space as u64, ctx.accounts.token_2022_program.key,
-        ),
-        &[ctx.accounts.host.to_account_info(), ctx.accounts.nft_mint.to_account_info(), ctx.accounts.system_program.to_account_info()],
-    )?;
-
-    // Initialize Transfer Hook - DISABLED to fix "Missing Account" error
-    /*
-    let ix_init_hook = spl_token_2022::extension::transfer_hook::instruction::initialize(
-        ctx.accounts.token_2022_program.key,
-        &mint_key,
-        Some(ctx.accounts.integration_config.key()),
-        Some(*ctx.program_id),
-    )?;
-    invoke(&ix_init_hook, &[ctx.accounts.nft_mint.to_account_info()])?;
-    */
-
-    // Initialize Mint
-    token_2022::initialize_mint(
-        CpiContext::new(ctx.accounts.token_2022_program.to_account_info(), token_2022::InitializeMint {
-            mint: ctx.accounts.nft_mint.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-        }),
-        0, &ctx.accounts.integration_config.key(), None,
-    )?;
-
-    // Create ATA
-    invoke(
-        &spl_associated_token_account::instruction::create_associated_token_account(
-            ctx.accounts.host.key, ctx.accounts.host.key, &mint_key, ctx.accounts.token_2022_program.key,
-        ),
-        &[
-            ctx.accounts.host.to_account_info(), ctx.accounts.host_ata.to_account_info(), ctx.accounts.nft_mint.to_account_info(), 
-            ctx.accounts.system_program.to_account_info(), ctx.accounts.associated_token_program.to_account_info(), ctx.accounts.token_2022_program.to_account_info()
-        ],
-    )?;
+    let oracle = ctx.accounts.protocol_config.oracle;
+    let message = booking_data.signed_message()?; // Rejects unsupported schema versions
+    let booking = booking_data.attest(&oracle, &ctx.accounts.host.key(), Clock::get()?.unix_timestamp)?;
+    verify_preceding_signature(&ctx.accounts.sysvar_instructions, &oracle, &message)?;
+
+    // Burn the nonce so the same signature can never mint twice
+    let nonce = booking.proof.nonce;
+    let nonce_page = &mut ctx.accounts.nonce_page;
+    if nonce_page.oracle == Pubkey::default() {
+        nonce_page.oracle = oracle;
+        nonce_page.page = nonce / NONCES_PER_PAGE;
+        nonce_page.bump = ctx.bumps.nonce_page;
+    }
+    nonce_page.consume(nonce)?;
 
     // --------------------------------------------
-    // PHASE 3: MINTING & DATA PERSISTENCE
+    // PHASE 2: MANUAL TOKEN-2022 SETUP & MINTING
     // --------------------------------------------
-    let seeds: &[&[u8]] = &[b"integrator", ctx.accounts.integration_wallet.key.as_ref(), &[ctx.accounts.integration_config.bump]];
-
-    token_2022::mint_to(
-        CpiContext::new_with_signer(ctx.accounts.token_2022_program.to_account_info(), MintTo {
-            mint: ctx.accounts.nft_mint.to_account_info(),
-            to: ctx.accounts.host_ata.to_account_info(),
-            authority: ctx.accounts.integration_config.to_account_info(),
-        }, &[seeds]),
-        1
-    )?;
+    let accounts = &ctx.accounts;
+    BookingNftAccounts {
+        host: &accounts.host.to_account_info(),
+        nft_mint: &accounts.nft_mint,
+        host_ata: &accounts.host_ata.to_account_info(),
+        integration_config: &accounts.integration_config,
+        integration_wallet: &accounts.integration_wallet.to_account_info(),
+        token_2022_program: &accounts.token_2022_program.to_account_info(),
+        associated_token_program: &accounts.associated_token_program.to_account_info(),
+        system_program: &accounts.system_program.to_account_info(),
+        rent: &accounts.rent.to_account_info(),
+    }
+    .mint_to_host(&[])?; // The mint is a transaction signer
 
+    // --------------------------------------------
+    // PHASE 3: DATA PERSISTENCE
+    // --------------------------------------------
+    let integrator_wallet = ctx.accounts.integration_wallet.key();
+    let mint_key = ctx.accounts.nft_mint.key();
+    let property = booking.link_property(&ctx.accounts.integration_config.key(), ctx.accounts.property.as_mut())?;
     let obligation = &mut ctx.accounts.booking_obligation;
-    obligation.tier = match booking_data.tier_index {
-        0 => ProfitTier::OneMonth,
-        1 => ProfitTier::ThreeMonth,
-        2 => ProfitTier::SixMonth,
-        3 => ProfitTier::TwelveMonth,
-        _ => return err!(ErrorCode::InvalidTier),
-    };
-    obligation.booking_id = booking_data.booking_id;
-    obligation.booking_value = booking_data.amount;
-    obligation.start_date = booking_data.start_date;
-    obligation.end_date = booking_data.end_date;
-    obligation.integrator_wallet=booking_data.investor_wallet;
-    obligation.host_wallet=booking_data.host_wallet;
-    obligation.nft_mint = mint_key;
-    obligation.bump = ctx.bumps.booking_obligation;
+    obligation.record_booking(booking, integrator_wallet, mint_key, ctx.bumps.booking_obligation);
+    obligation.property = property;
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_2022::Token2022};
+use crate::constants::{INTEGRATOR_VERSION, MAX_ROOT_VALIDITY, NONCES_PER_PAGE, PAUSE_MINT};
+use crate::error::ErrorCode;
+use crate::handlers::mint_booking::BookingNftAccounts;
+use crate::merkle::{leaf_hash, verify_proof};
+use crate::proof::BookingProofEnvelope;
+use crate::state::{BookingBatch, BookingObligation, IntegratorBond, IntegratorConfig, NonceBitmap, Property, ProtocolConfig};
+
+/// mint_booking, attested by a leaf of an oracle-signed root instead of its own
+/// Ed25519 signature.
+#[derive(Accounts)]
+#[instruction(booking_data: BookingProofEnvelope)]
+pub struct MintBookingFromRoot<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = integration_config.is_active @ ErrorCode::IntegratorNotAuthorized
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_MINT) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    // The root this booking is a leaf of; a rotated oracle retires its roots
+    #[account(
+        mut,
+        seeds = [b"booking_batch", integration_config.key().as_ref(), &booking_batch.batch_id.to_le_bytes()],
+        bump = booking_batch.bump,
+        constraint = booking_batch.oracle == protocol_config.oracle @ ErrorCode::InvalidOracleKey,
+    )]
+    pub booking_batch: Account<'info, BookingBatch>,
+
+    // --- Property: the listing this booking is for (required if the proof names one) ---
+    #[account(mut)]
+    pub property: Option<Account<'info, Property>>,
+
+    /// CHECK: Manually created via System Program to support Token-2022 Extensions
+    #[account(mut, signer)]
+    pub nft_mint: AccountInfo<'info>,
+
+    /// CHECK: Manually initialized via Associated Token Program after Mint creation
+    #[account(mut)]
+    pub host_ata: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = host,
+        space = 8 + BookingObligation::INIT_SPACE,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump
+    )]
+    pub booking_obligation: Account<'info, BookingObligation>,
+
+    // --- Replay Guard: nonces stay single-use across every mint path ---
+    #[account(
+        init_if_needed,
+        payer = host,
+        space = 8 + NonceBitmap::INIT_SPACE,
+        seeds = [
+            b"nonce_page",
+            protocol_config.oracle.as_ref(),
+            &(booking_data.proof.nonce / NONCES_PER_PAGE).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub nonce_page: Account<'info, NonceBitmap>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<MintBookingFromRoot>,
+    booking_data: BookingProofEnvelope,
+    leaf_index: u32,
+    merkle_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    // --------------------------------------------
+    // PHASE 0: INTEGRATOR SKIN IN THE GAME
+    // --------------------------------------------
+    let required_bond = ctx.accounts.protocol_config
+        .required_bond(ctx.accounts.integration_config.outstanding_principal)?;
+    require!(
+        ctx.accounts.integrator_bond.amount >= required_bond,
+        ErrorCode::InsufficientIntegratorBond
+    );
+
+    // --------------------------------------------
+    // PHASE 1: ROOT VERIFICATION
+    // --------------------------------------------
+    let now = Clock::get()?.unix_timestamp;
+    let oracle = ctx.accounts.protocol_config.oracle;
+    let batch = &mut ctx.accounts.booking_batch;
+    require!(now <= batch.expires_at, ErrorCode::BookingRootExpired);
+
+    let message = booking_data.signed_message()?; // Rejects unsupported schema versions
+    let booking = booking_data.attest_within(&oracle, &ctx.accounts.host.key(), now, MAX_ROOT_VALIDITY)?;
+    verify_proof(&batch.root, batch.leaf_count, leaf_hash(&message), leaf_index, &merkle_proof)?;
+    batch.claim(leaf_index)?; // Each leaf mints once
+
+    // Burn the nonce too, so the same proof can't also mint through another root or mint_booking
+    let nonce = booking.proof.nonce;
+    let nonce_page = &mut ctx.accounts.nonce_page;
+    if nonce_page.oracle == Pubkey::default() {
+        nonce_page.oracle = oracle;
+        nonce_page.page = nonce / NONCES_PER_PAGE;
+        nonce_page.bump = ctx.bumps.nonce_page;
+    }
+    nonce_page.consume(nonce)?;
+
+    // --------------------------------------------
+    // PHASE 2: MANUAL TOKEN-2022 SETUP & MINTING
+    // --------------------------------------------
+    let accounts = &ctx.accounts;
+    BookingNftAccounts {
+        host: &accounts.host.to_account_info(),
+        nft_mint: &accounts.nft_mint,
+        host_ata: &accounts.host_ata.to_account_info(),
+        integration_config: &accounts.integration_config,
+        integration_wallet: &accounts.integration_wallet.to_account_info(),
+        token_2022_program: &accounts.token_2022_program.to_account_info(),
+        associated_token_program: &accounts.associated_token_program.to_account_info(),
+        system_program: &accounts.system_program.to_account_info(),
+        rent: &accounts.rent.to_account_info(),
+    }
+    .mint_to_host(&[])?; // The mint is a transaction signer
+
+    // --------------------------------------------
+    // PHASE 3: DATA PERSISTENCE
+    // --------------------------------------------
+    let integrator_wallet = ctx.accounts.integration_wallet.key();
+    let mint_key = ctx.accounts.nft_mint.key();
+    let property = booking.link_property(&ctx.accounts.integration_config.key(), ctx.accounts.property.as_mut())?;
+    let obligation = &mut ctx.accounts.booking_obligation;
+    obligation.record_booking(booking, integrator_wallet, mint_key, ctx.bumps.booking_obligation);
+    obligation.property = property;
+
+    msg!("Booking Minted From Root: leaf {} of batch {}", leaf_index, ctx.accounts.booking_batch.batch_id);
+    Ok(())
+}
@@ -0,0 +1,264 @@
+/*
+  Batch Booking Mint 📦
+
+  A property manager with many listings mints several bookings in one
+  instruction instead of one transaction (and one Ed25519 instruction) each.
+
+  The preceding Ed25519 instruction attests the whole batch, either with:
+    - one oracle signature over `batch_signed_message` (all proofs, in order), or
+    - one oracle signature per proof, packed into the same instruction.
+
+  Each booking brings its accounts through remaining_accounts, in order:
+    [nft_mint, host_ata, booking_obligation, nonce_page, property] x bookings.len()
+  where `property` is this program's id when the booking has none (Anchor's
+  convention for an omitted optional account).
+
+  The NFT mints are PDAs (["booking_mint", oracle, nonce]) so the batch needs
+  no extra signatures. Transaction size caps a batch long before compute does,
+  but compute is still checked before each booking so an oversized batch fails
+  with a clear error rather than running out mid-mint.
+*/
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::{associated_token::AssociatedToken, token_2022::Token2022};
+use spl_token_2022::solana_program::compute_units::sol_remaining_compute_units;
+use crate::constants::{
+    BATCH_ACCOUNTS_PER_BOOKING, BOOKING_MINT_COMPUTE_UNITS, INTEGRATOR_VERSION, MAX_BATCH_BOOKINGS,
+    NONCES_PER_PAGE, PAUSE_MINT,
+};
+use crate::ed25519::verify_preceding_batch;
+use crate::error::ErrorCode;
+use crate::handlers::mint_booking::BookingNftAccounts;
+use crate::proof::{batch_signed_message, BookingProofEnvelope};
+use crate::state::{BookingObligation, IntegratorBond, IntegratorConfig, NonceBitmap, Property, ProtocolConfig};
+
+#[derive(Accounts)]
+pub struct MintBookingsBatch<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = integration_config.is_active @ ErrorCode::IntegratorNotAuthorized
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_MINT) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Instruction Introspection Sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub sysvar_instructions: AccountInfo<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    // remaining_accounts: [nft_mint, host_ata, booking_obligation, nonce_page, property] per booking
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintBookingsBatch<'info>>,
+    bookings: Vec<BookingProofEnvelope>,
+) -> Result<()> {
+    // 1. LIMITS: Bounded batch, five accounts per booking
+    require!(
+        !bookings.is_empty() && bookings.len() <= MAX_BATCH_BOOKINGS,
+        ErrorCode::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == bookings.len() * BATCH_ACCOUNTS_PER_BOOKING,
+        ErrorCode::BatchAccountsMismatch
+    );
+
+    // 2. SKIN IN THE GAME: Minting adds no debt, so one bond check covers the batch
+    let required_bond = ctx.accounts.protocol_config
+        .required_bond(ctx.accounts.integration_config.outstanding_principal)?;
+    require!(
+        ctx.accounts.integrator_bond.amount >= required_bond,
+        ErrorCode::InsufficientIntegratorBond
+    );
+
+    // 3. ORACLE: Every proof checked, the whole batch attested by the preceding Ed25519 instruction
+    let oracle = ctx.accounts.protocol_config.oracle;
+    let host = ctx.accounts.host.key();
+    let now = Clock::get()?.unix_timestamp;
+    let messages = bookings
+        .iter()
+        .map(BookingProofEnvelope::signed_message) // Rejects unsupported schema versions
+        .collect::<Result<Vec<_>>>()?;
+    verify_preceding_batch(
+        &ctx.accounts.sysvar_instructions,
+        &oracle,
+        &messages,
+        &batch_signed_message(&messages),
+    )?;
+
+    // 4. MINT: One NFT + obligation per booking
+    let accounts = &ctx.accounts;
+    let host_info = accounts.host.to_account_info();
+    let system_program = accounts.system_program.to_account_info();
+    let integration_wallet = accounts.integration_wallet.key();
+    for (i, (envelope, booking_accounts)) in bookings
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(BATCH_ACCOUNTS_PER_BOOKING))
+        .enumerate()
+    {
+        if sol_remaining_compute_units() < BOOKING_MINT_COMPUTE_UNITS {
+            msg!("Compute exhausted before booking #{} of {}", i, bookings.len());
+            return err!(ErrorCode::BatchComputeExhausted);
+        }
+
+        let booking = envelope.attest(&oracle, &host, now)?;
+        let nonce = booking.proof.nonce;
+        let [nft_mint, host_ata, obligation_info, nonce_info, property_info] = booking_accounts else {
+            return err!(ErrorCode::BatchAccountsMismatch);
+        };
+
+        // Burn the nonce (pages are shared between bookings, so write back right away)
+        let page = nonce / NONCES_PER_PAGE;
+        let page_bytes = page.to_le_bytes();
+        let page_seeds: &[&[u8]] = &[b"nonce_page", oracle.as_ref(), &page_bytes];
+        let page_bump = pda_bump(nonce_info, page_seeds)?;
+        if nonce_info.owner != &crate::ID {
+            create_pda::<NonceBitmap>(
+                &host_info, nonce_info, &system_program,
+                &[page_seeds, &[&[page_bump]]].concat(),
+                8 + NonceBitmap::INIT_SPACE,
+            )?;
+        }
+        let mut nonce_page = Account::<NonceBitmap>::try_from(nonce_info)?;
+        if nonce_page.oracle == Pubkey::default() {
+            nonce_page.oracle = oracle;
+            nonce_page.page = page;
+            nonce_page.bump = page_bump;
+        }
+        nonce_page.consume(nonce)?;
+        nonce_page.exit(&crate::ID)?;
+
+        // The NFT, minted to the host under a PDA mint
+        let nonce_bytes = nonce.to_le_bytes();
+        let mint_seeds: &[&[u8]] = &[b"booking_mint", oracle.as_ref(), &nonce_bytes];
+        let mint_bump = pda_bump(nft_mint, mint_seeds)?;
+        BookingNftAccounts {
+            host: &host_info,
+            nft_mint,
+            host_ata,
+            integration_config: &accounts.integration_config,
+            integration_wallet: &accounts.integration_wallet.to_account_info(),
+            token_2022_program: &accounts.token_2022_program.to_account_info(),
+            associated_token_program: &accounts.associated_token_program.to_account_info(),
+            system_program: &system_program,
+            rent: &accounts.rent.to_account_info(),
+        }
+        .mint_to_host(&[&[mint_seeds, &[&[mint_bump]]].concat()])?;
+
+        // The obligation, exactly as mint_booking records it
+        let mint_key = nft_mint.key();
+        let obligation_seeds: &[&[u8]] = &[b"obligation", mint_key.as_ref()];
+        let obligation_bump = pda_bump(obligation_info, obligation_seeds)?;
+        create_pda::<BookingObligation>(
+            &host_info, obligation_info, &system_program,
+            &[obligation_seeds, &[&[obligation_bump]]].concat(),
+            8 + BookingObligation::INIT_SPACE,
+        )?;
+        let mut property = if property_info.key() == crate::ID {
+            None
+        } else {
+            require!(property_info.is_writable, ErrorCode::BatchAccountsMismatch);
+            Some(Account::<Property>::try_from(property_info)?)
+        };
+        let property_key = booking.link_property(&accounts.integration_config.key(), property.as_mut())?;
+        if let Some(property) = property {
+            property.exit(&crate::ID)?;
+        }
+
+        let mut obligation = Account::<BookingObligation>::try_from(obligation_info)?;
+        obligation.record_booking(booking, integration_wallet, mint_key, obligation_bump);
+        obligation.property = property_key;
+        obligation.exit(&crate::ID)?;
+    }
+
+    msg!("Booking Batch Minted: {} bookings for {}", bookings.len(), host);
+    Ok(())
+}
+
+/// Bump of `account` as the PDA of `seeds`; fails if it is some other address.
+fn pda_bump(account: &AccountInfo, seeds: &[&[u8]]) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(account.key(), expected, ErrorCode::BatchAccountsMismatch);
+    Ok(bump)
+}
+
+/// What Anchor's `init` does: allocates a program-owned PDA (even if someone
+/// pre-funded the address) and writes `T`'s discriminator over zeroed data.
+fn create_pda<'info, T: Discriminator>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    let signer: &[&[&[u8]]] = &[seeds];
+    let required = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount { from: payer.clone(), to: account.clone() },
+                signer,
+            ),
+            required,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        require_keys_eq!(*account.owner, system_program::ID, ErrorCode::BatchAccountsMismatch);
+        let shortfall = required.saturating_sub(account.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer { from: payer.clone(), to: account.clone() },
+                ),
+                shortfall,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate { account_to_allocate: account.clone() },
+                signer,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign { account_to_assign: account.clone() },
+                signer,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    account.try_borrow_mut_data()?[..8].copy_from_slice(T::DISCRIMINATOR);
+    Ok(())
+}
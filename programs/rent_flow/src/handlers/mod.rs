@@ -1,20 +1,73 @@
 pub mod initialize;
 pub mod init_vault;
+pub mod set_currency_feed;
+pub mod set_mock_price;
 pub mod mint_booking;
+pub mod mint_bookings_batch;
+pub mod post_booking_root;
+pub mod mint_booking_from_root;
+pub mod close_booking_batch;
+pub mod register_property;
 pub mod lock_cycle;
 pub mod deposit_collateral;
 pub mod withdraw_liquidity;
 pub mod settle_booking;
 pub mod withdraw_collateral;
 pub mod liquidate_default;
+pub mod init_protocol;
+pub mod liquidated_asset_auction;
+pub mod write_off;
+pub mod redeem_defaulted;
+pub mod pool_stats;
+pub mod post_integrator_bond;
+pub mod set_integrator_status;
+pub mod request_unbond;
+pub mod withdraw_bond;
+pub mod cure_host_default;
+pub mod pause;
+pub mod unpause;
+pub mod set_guardian;
+pub mod queue_change;
+pub mod execute_change;
+pub mod cancel_change;
+pub mod transfer_admin;
+pub mod create_multisig;
+pub mod propose_admin_action;
+pub mod approve_admin_proposal;
+pub mod execute_admin_proposal;
+pub mod migrate_obligation;
+pub mod migrate_position;
+pub mod migrate_pool_vault;
+pub mod migrate_supported_token;
+pub mod migrate_integrator;
+pub mod migrate_pool_liquidity;
+pub mod deposit_liquidity;
+pub mod settle_on_behalf;
+pub mod fund_repayment_escrow;
+pub mod crank_settle;
+pub mod withdraw_repayment_escrow;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
 #[allow(ambiguous_glob_reexports)]
 pub use init_vault::*;
 #[allow(ambiguous_glob_reexports)]
+pub use set_currency_feed::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_mock_price::*;
+#[allow(ambiguous_glob_reexports)]
 pub use mint_booking::*;
 #[allow(ambiguous_glob_reexports)]
+pub use mint_bookings_batch::*;
+#[allow(ambiguous_glob_reexports)]
+pub use post_booking_root::*;
+#[allow(ambiguous_glob_reexports)]
+pub use mint_booking_from_root::*;
+#[allow(ambiguous_glob_reexports)]
+pub use close_booking_batch::*;
+#[allow(ambiguous_glob_reexports)]
+pub use register_property::*;
+#[allow(ambiguous_glob_reexports)]
 pub use lock_cycle::*;
 #[allow(ambiguous_glob_reexports)]
 pub use deposit_collateral::*;
@@ -24,3 +77,67 @@ pub use withdraw_liquidity::*;
 pub use settle_booking::*;
 #[allow(ambiguous_glob_reexports)]
 pub use liquidate_default::*;
+#[allow(ambiguous_glob_reexports)]
+pub use init_protocol::*;
+#[allow(ambiguous_glob_reexports)]
+pub use liquidated_asset_auction::*;
+#[allow(ambiguous_glob_reexports)]
+pub use write_off::*;
+#[allow(ambiguous_glob_reexports)]
+pub use redeem_defaulted::*;
+#[allow(ambiguous_glob_reexports)]
+pub use pool_stats::*;
+#[allow(ambiguous_glob_reexports)]
+pub use post_integrator_bond::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_integrator_status::*;
+#[allow(ambiguous_glob_reexports)]
+pub use request_unbond::*;
+#[allow(ambiguous_glob_reexports)]
+pub use withdraw_bond::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cure_host_default::*;
+#[allow(ambiguous_glob_reexports)]
+pub use pause::*;
+#[allow(ambiguous_glob_reexports)]
+pub use unpause::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_guardian::*;
+#[allow(ambiguous_glob_reexports)]
+pub use queue_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use execute_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_change::*;
+#[allow(ambiguous_glob_reexports)]
+pub use transfer_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use create_multisig::*;
+#[allow(ambiguous_glob_reexports)]
+pub use propose_admin_action::*;
+#[allow(ambiguous_glob_reexports)]
+pub use approve_admin_proposal::*;
+#[allow(ambiguous_glob_reexports)]
+pub use execute_admin_proposal::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_obligation::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_position::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_pool_vault::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_supported_token::*;
+#[allow(ambiguous_glob_reexports)]
+pub use deposit_liquidity::*;
+#[allow(ambiguous_glob_reexports)]
+pub use settle_on_behalf::*;
+#[allow(ambiguous_glob_reexports)]
+pub use fund_repayment_escrow::*;
+#[allow(ambiguous_glob_reexports)]
+pub use crank_settle::*;
+#[allow(ambiguous_glob_reexports)]
+pub use withdraw_repayment_escrow::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_integrator::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_pool_liquidity::*;
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::constants::PAUSE_ALL;
+use crate::state::ProtocolConfig;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        // RULE: The guardian (or admin) may pause; it can never unpause
+        constraint = authority.key() == protocol_config.guardian
+            || authority.key() == protocol_config.admin @ ErrorCode::NotGuardian,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<Pause>, mask: u8) -> Result<()> {
+    require!(mask != 0 && mask & !PAUSE_ALL == 0, ErrorCode::InvalidPauseMask);
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.paused |= mask;
+
+    msg!("🚨 Protocol Paused: mask {:#08b} (now {:#08b})", mask, config.paused);
+    Ok(())
+}
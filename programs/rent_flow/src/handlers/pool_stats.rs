@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::constants::{BPS_DENOMINATOR, POOL_VAULT_VERSION, SUPPORTED_TOKEN_VERSION};
+use crate::state::{PoolVault, ReserveFund, SupportedToken};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct GetPoolStats<'info> {
+    #[account(
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+
+    // Stats are per currency: the books of this mint's lending pool
+    #[account(
+        seeds = [b"vault", mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+
+    #[account(
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        associated_token::authority = reserve_fund,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Read-only snapshot of pool health, returned to the caller via return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolStats {
+    pub total_liquidity_tracked: u64,
+    pub outstanding_principal: u64,
+    pub reserve_balance: u64,
+    pub reserve_total_contributed: u64,
+    pub reserve_total_covered: u64,
+    /// Reserve balance over outstanding principal; u64::MAX when nothing is lent out.
+    pub coverage_ratio_bps: u64,
+    pub utilization_bps: u64,
+    /// Rate a new loan would lock in right now, before host reputation.
+    pub borrow_rate_bps: u64,
+    /// Lifetime bad debt written off LP principal.
+    pub total_losses: u64,
+}
+
+pub fn handler(ctx: Context<GetPoolStats>) -> Result<PoolStats> {
+    let pool = &ctx.accounts.supported_token;
+    let reserve = &ctx.accounts.reserve_fund;
+    let reserve_balance = ctx.accounts.reserve_token_ata.amount;
+
+    let coverage_ratio_bps = if pool.outstanding_principal == 0 {
+        u64::MAX
+    } else {
+        let ratio = (reserve_balance as u128)
+            .checked_mul(BPS_DENOMINATOR as u128).ok_or(ErrorCode::MathOverflow)?
+            .checked_div(pool.outstanding_principal as u128).ok_or(ErrorCode::MathOverflow)?;
+        u64::try_from(ratio).unwrap_or(u64::MAX)
+    };
+
+    let utilization_bps = pool.utilization_bps(0)?;
+    let borrow_rate_bps = ctx.accounts.pool_vault.rate_model.borrow_rate_bps(utilization_bps)?;
+
+    Ok(PoolStats {
+        total_liquidity_tracked: pool.total_liquidity_tracked,
+        outstanding_principal: pool.outstanding_principal,
+        reserve_balance,
+        reserve_total_contributed: reserve.total_contributed,
+        reserve_total_covered: reserve.total_covered,
+        coverage_ratio_bps,
+        utilization_bps,
+        borrow_rate_bps,
+        total_losses: pool.total_losses,
+    })
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::constants::{INTEGRATOR_VERSION, MAX_ROOT_LEAVES, MAX_ROOT_VALIDITY, PAUSE_MINT};
+use crate::ed25519::verify_preceding_signature;
+use crate::error::ErrorCode;
+use crate::state::{BookingBatch, IntegratorConfig, ProtocolConfig};
+
+/// Domain tag prefixed to the borsh bytes of a BookingRootAttestation before signing.
+pub const BOOKING_ROOT_DOMAIN: &[u8] = b"rent_flow:booking_root:v1";
+
+/// What the oracle signs: this integrator's batch of bookings has this Merkle root
+/// (see `merkle` for how leaves and nodes are hashed).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct BookingRootAttestation {
+    pub integrator_config: Pubkey,
+    pub batch_id: u64,
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub expires_at: i64,
+}
+
+impl BookingRootAttestation {
+    /// Canonical signed bytes: domain tag || borsh(self).
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = BOOKING_ROOT_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(batch_id: u64)]
+pub struct PostBookingRoot<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = integration_config.is_active @ ErrorCode::IntegratorNotAuthorized,
+        has_one = authority @ ErrorCode::IntegratorNotAuthorized,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_MINT) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BookingBatch::INIT_SPACE,
+        seeds = [b"booking_batch", integration_config.key().as_ref(), &batch_id.to_le_bytes()],
+        bump
+    )]
+    pub booking_batch: Account<'info, BookingBatch>,
+
+    /// CHECK: Instruction Introspection Sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub sysvar_instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PostBookingRoot>,
+    batch_id: u64,
+    root: [u8; 32],
+    leaf_count: u32,
+    expires_at: i64,
+) -> Result<()> {
+    // 1. SHAPE: Fits the claimed-leaf bitmap, and expires within the window
+    let now = Clock::get()?.unix_timestamp;
+    require!(leaf_count > 0 && leaf_count <= MAX_ROOT_LEAVES, ErrorCode::InvalidBookingRoot);
+    require!(
+        expires_at > now && expires_at - now <= MAX_ROOT_VALIDITY,
+        ErrorCode::InvalidBookingRoot
+    );
+
+    // 2. ORACLE: The root must be attested for this integrator and batch
+    let oracle = ctx.accounts.protocol_config.oracle;
+    let attestation = BookingRootAttestation {
+        integrator_config: ctx.accounts.integration_config.key(),
+        batch_id,
+        root,
+        leaf_count,
+        expires_at,
+    };
+    verify_preceding_signature(&ctx.accounts.sysvar_instructions, &oracle, &attestation.message()?)?;
+
+    // 3. RECORD
+    let batch = &mut ctx.accounts.booking_batch;
+    batch.integrator_config = attestation.integrator_config;
+    batch.batch_id = batch_id;
+    batch.oracle = oracle;
+    batch.root = root;
+    batch.leaf_count = leaf_count;
+    batch.posted_at = now;
+    batch.expires_at = expires_at;
+    batch.bump = ctx.bumps.booking_batch;
+
+    msg!("Booking Root Posted: batch {} with {} leaves", batch_id, leaf_count);
+    Ok(())
+}
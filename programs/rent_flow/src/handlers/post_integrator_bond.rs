@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+use crate::constants::{INTEGRATOR_BOND_VERSION, INTEGRATOR_VERSION};
+use crate::state::{IntegratorBond, IntegratorConfig};
+use crate::error::ErrorCode;
+use crate::token_extensions::net_of_fee;
+
+#[derive(Accounts)]
+pub struct PostIntegratorBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        has_one = authority @ ErrorCode::IntegratorNotAuthorized,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + IntegratorBond::INIT_SPACE,
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // The bond's escrow: owned by the IntegratorBond PDA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::authority = integrator_bond,
+        associated_token::mint = usdc_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub bond_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PostIntegratorBond>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let bond = &mut ctx.accounts.integrator_bond;
+    if bond.integrator_config == Pubkey::default() {
+        // First deposit: pin the bond to its integrator and currency
+        bond.integrator_config = ctx.accounts.integration_config.key();
+        bond.mint = ctx.accounts.usdc_mint.key();
+        bond.bump = ctx.bumps.integrator_bond;
+        bond.version = INTEGRATOR_BOND_VERSION;
+    }
+    require_keys_eq!(bond.mint, ctx.accounts.usdc_mint.key(), ErrorCode::MintMismatch);
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.authority_usdc_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.bond_usdc_ata.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    // Transfer-fee mints: only what lands in the bond account backs the integrator
+    let amount = net_of_fee(&ctx.accounts.usdc_mint.to_account_info(), amount)?;
+    let bond = &mut ctx.accounts.integrator_bond;
+    bond.amount = bond.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Integrator Bond Posted: {} (total {})", amount, bond.amount);
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_PROPOSAL_ACCOUNTS;
+use crate::events::AdminProposalCreated;
+use crate::state::{AdminAction, AdminMultisig, AdminProposal, ProposalAccount};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_multisig"],
+        bump = multisig.bump,
+        constraint = multisig.signer_index(&proposer.key()).is_some() @ ErrorCode::NotMultisigMember,
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + AdminProposal::INIT_SPACE,
+        seeds = [b"admin_proposal", multisig.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeAdminAction>,
+    action: AdminAction,
+    accounts: Vec<ProposalAccount>,
+) -> Result<()> {
+    require!(accounts.len() <= MAX_PROPOSAL_ACCOUNTS, ErrorCode::ProposalAccountsMismatch);
+    if let AdminAction::QueueChange { change } = action {
+        change.validate()?;
+    }
+
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer = ctx.accounts.proposer.key();
+    let index = multisig.signer_index(&proposer).ok_or(ErrorCode::NotMultisigMember)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = multisig.proposal_count;
+    proposal.proposer = proposer;
+    proposal.action = action;
+    proposal.accounts = accounts;
+    proposal.approvals = 1 << index; // Proposing counts as the first approval
+    proposal.bump = ctx.bumps.proposal;
+
+    multisig.proposal_count = multisig.proposal_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(AdminProposalCreated { id: proposal.id, proposer, action });
+    msg!("Admin Proposal #{} Created: {:?}", proposal.id, action);
+    Ok(())
+}
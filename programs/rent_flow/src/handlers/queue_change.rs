@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::events::ChangeQueued;
+use crate::state::{ParamChange, PendingChange, ProtocolConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct QueueChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PendingChange::INIT_SPACE,
+        seeds = [b"pending_change", protocol_config.change_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueChange>, change: ParamChange) -> Result<()> {
+    change.validate()?;
+
+    let config = &mut ctx.accounts.protocol_config;
+    let eta = Clock::get()?.unix_timestamp
+        .checked_add(config.timelock_delay)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let pending = &mut ctx.accounts.pending_change;
+    pending.id = config.change_count;
+    pending.change = change;
+    pending.eta = eta;
+    pending.proposer = ctx.accounts.admin.key();
+    pending.bump = ctx.bumps.pending_change;
+
+    config.change_count = config.change_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(ChangeQueued { id: pending.id, change, eta });
+    msg!("Change #{} Queued, executable at {}", pending.id, eta);
+    Ok(())
+}
@@ -0,0 +1,366 @@
+/*
+  Host Right to Cure 🔑
+
+  The Problem: Once `liquidate_default` moves the NFT to the pool, a host whose
+  platform payout lands a day late has no way back.
+
+  The Solution: For RedemptionTerms.window after seizure the original host can
+  buy the booking back: the regular settlement (principal + interest) plus a
+  default penalty and any liquidation bounty. Auctions wait for the window.
+
+  The loss never happened, so the integrator's slashed bond is returned: from
+  the pool's vault when it is in the loan's currency, otherwise from the reserve
+  fund, as far as the reserve has not already spent it covering other losses.
+*/
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{self, Token2022},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+use crate::constants::{INTEGRATOR_VERSION, OBLIGATION_VERSION, PAUSE_SETTLE, POOL_VAULT_VERSION, SUPPORTED_TOKEN_VERSION};
+use crate::events::BookingSettled;
+use crate::handlers::settle_booking::SettlementSplit;
+use crate::state::{BookingObligation, HostProfile, IntegratorBond, IntegratorConfig, ObligationStatus, PoolVault, Property, ProtocolConfig, ReserveFund, SupportedToken};
+use crate::error::ErrorCode;
+use crate::token_extensions::{gross_up, net_of_fee};
+use crate::math::bps_of;
+
+#[derive(Accounts)]
+pub struct RedeemDefaulted<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = obligation.status == ObligationStatus::Defaulted @ ErrorCode::NotDefaulted,
+        constraint = obligation.borrowed_amount > 0 @ ErrorCode::LoanNotFunded, // Nothing to redeem, nor to earn reputation on
+        constraint = obligation.host_wallet == host.key() @ ErrorCode::NotHubOwner,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub host_token_ata: InterfaceAccount<'info, TokenAccount>, // Source of Cash
+
+    // --- Pool Corridor: debt + LP yield in, slashed bond out ---
+    // Custodian of seized NFTs, whatever the loan currency
+    #[account(
+        seeds = [b"pool_vault"],
+        bump = pool_vault.bump,
+        constraint = pool_vault.version == POOL_VAULT_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub pool_vault: Account<'info, PoolVault>,
+
+    // The lending pool of the loan's currency
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Treasury / Reserve Corridors ---
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_SETTLE) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        associated_token::authority = protocol_config,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Bond Corridor (Pool or Reserve -> Bond, slash refund) ---
+    #[account(
+        mut,
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+    #[account(
+        mut,
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+    #[account(address = integrator_bond.mint @ ErrorCode::MintMismatch)]
+    pub bond_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::authority = integrator_bond,
+        associated_token::mint = bond_mint,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub bond_token_ata: InterfaceAccount<'info, TokenAccount>,
+    // A bond in another currency than the loan was slashed into the reserve instead
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = bond_mint,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub reserve_bond_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Bounty Corridor (Host -> Liquidator, deferred bounties only) ---
+    /// CHECK: Only receives the deferred bounty
+    #[account(address = obligation.liquidator)]
+    pub liquidator: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = host,
+        associated_token::authority = liquidator,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub liquidator_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"host_profile", host.key().as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
+
+    // --- Asset Corridor (Pool -> Host) ---
+    #[account(
+        mut,
+        associated_token::authority = pool_vault,
+        associated_token::mint = nft_mint,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub pool_nft_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = host,
+        associated_token::authority = host,
+        associated_token::mint = nft_mint,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub host_nft_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,      // For the loan currency
+    pub bond_token_program: Interface<'info, TokenInterface>, // For the bond currency
+    pub token_2022_program: Program<'info, Token2022>,     // For NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RedeemDefaulted>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= ctx.accounts.obligation.redeem_deadline, ErrorCode::RedemptionWindowClosed);
+
+    // 1. THE BILL: Regular settlement with the default penalty in place of the early-exit one
+    let obligation = &ctx.accounts.obligation;
+    let principal = obligation.borrowed_amount;
+    let interest = bps_of(principal, obligation.rate_bps)?;
+    let penalty = bps_of(principal, ctx.accounts.protocol_config.redemption.penalty_bps as u64)?;
+    let split = SettlementSplit::new(principal, interest, penalty, &ctx.accounts.protocol_config)?;
+    let bounty_paid = obligation.bounty_paid;
+    let bounty_owed = obligation.bounty_owed;
+    let bond_slashed = obligation.bond_slashed;
+    let reserve_slashed = obligation.reserve_slashed;
+    let decimals = ctx.accounts.loan_mint.decimals;
+
+    // 2. REPAYMENT: Host -> Pool (incl. the advanced bounty), Treasury, Reserve, Liquidator
+    let to_pool = split.pool_amount()?
+        .checked_add(bounty_paid)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let legs = [
+        (ctx.accounts.token_vault.to_account_info(), to_pool),
+        (ctx.accounts.treasury_token_ata.to_account_info(), split.protocol_fee),
+        (ctx.accounts.reserve_token_ata.to_account_info(), split.reserve_cut),
+        (ctx.accounts.liquidator_token_ata.to_account_info(), bounty_owed),
+    ];
+    for (destination, amount) in legs {
+        if amount == 0 {
+            continue;
+        }
+        // Transfer-fee mints: the host covers the fee so each leg lands in full
+        let amount = gross_up(&ctx.accounts.loan_mint.to_account_info(), amount)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.host_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.host.to_account_info(),
+                },
+            ),
+            amount,
+            decimals,
+        )?;
+    }
+
+    // 3. UNDO THE SLASH + RELEASE: Vault or Reserve -> Bond, Pool -> Host (NFT)
+    let bond_refund = if bond_slashed > 0 {
+        let loan_mint_key = ctx.accounts.loan_mint.key();
+        let vault_seeds: &[&[&[u8]]] = &[&[b"vault", loan_mint_key.as_ref(), &[ctx.accounts.supported_token.bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: ctx.accounts.bond_token_ata.to_account_info(),
+                    authority: ctx.accounts.supported_token.to_account_info(),
+                },
+                vault_seeds,
+            ),
+            bond_slashed,
+            decimals,
+        )?;
+        bond_slashed
+    } else if reserve_slashed > 0 {
+        // What the reserve already paid out covering other losses stays spent
+        let refund = reserve_slashed.min(ctx.accounts.reserve_bond_ata.amount);
+        if refund > 0 {
+            let reserve_seeds: &[&[&[u8]]] = &[&[b"reserve_fund", &[ctx.accounts.reserve_fund.bump]]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.bond_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.reserve_bond_ata.to_account_info(),
+                        mint: ctx.accounts.bond_mint.to_account_info(),
+                        to: ctx.accounts.bond_token_ata.to_account_info(),
+                        authority: ctx.accounts.reserve_fund.to_account_info(),
+                    },
+                    reserve_seeds,
+                ),
+                refund,
+                ctx.accounts.bond_mint.decimals,
+            )?;
+            let reserve = &mut ctx.accounts.reserve_fund;
+            reserve.total_contributed = reserve.total_contributed.saturating_sub(refund);
+        }
+        refund
+    } else {
+        0
+    };
+
+    if bond_refund > 0 {
+        // The bond is credited with what lands; any transfer fee is the integrator's.
+        // total_slashed counts what left the bond's account, so it drops by what comes back to it.
+        let refunded = net_of_fee(&ctx.accounts.bond_mint.to_account_info(), bond_refund)?;
+        let bond = &mut ctx.accounts.integrator_bond;
+        bond.amount = bond.amount.checked_add(refunded).ok_or(ErrorCode::MathOverflow)?;
+        bond.total_slashed = bond.total_slashed.saturating_sub(refunded);
+        msg!(
+            "Integrator Bond Refunded: {} ({} kept by the reserve)",
+            refunded,
+            reserve_slashed.saturating_sub(bond_refund)
+        );
+    }
+
+    let pool_seeds: &[&[&[u8]]] = &[&[b"pool_vault", &[ctx.accounts.pool_vault.bump]]];
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            token_2022::TransferChecked {
+                from: ctx.accounts.pool_nft_ata.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.host_nft_ata.to_account_info(),
+                authority: ctx.accounts.pool_vault.to_account_info(),
+            },
+            pool_seeds,
+        ),
+        1, 0
+    )?;
+
+    // 4. BOOKS: Same exit as a settlement, plus the fee / reserve counters
+    if split.protocol_fee > 0 {
+        let config = &mut ctx.accounts.protocol_config;
+        config.total_fees_collected = config.total_fees_collected
+            .checked_add(split.protocol_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    if split.reserve_cut > 0 {
+        let reserve = &mut ctx.accounts.reserve_fund;
+        reserve.total_contributed = reserve.total_contributed
+            .checked_add(split.reserve_cut)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    let pool = &mut ctx.accounts.supported_token;
+    pool.outstanding_principal = pool.outstanding_principal
+        .checked_sub(principal.checked_add(bounty_paid).ok_or(ErrorCode::MathOverflow)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.distribute_yield(split.lp_yield)?;
+
+    let integrator = &mut ctx.accounts.integration_config;
+    integrator.outstanding_principal = integrator.outstanding_principal
+        .checked_sub(principal)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // 5. REPUTATION: A late repayment; the default stays in default_count but is cured
+    let obligation = &mut ctx.accounts.obligation;
+    let profile = &mut ctx.accounts.host_profile;
+    profile.record_repayment(obligation.end_date, now, principal, split.total()?)?;
+    if obligation.has_open_default() {
+        // Unless the admin already cured this default (cure_host_default)
+        profile.active_defaults = profile.active_defaults.saturating_sub(1);
+    }
+    if let Some(property) = Property::linked(obligation, &mut ctx.accounts.property)? {
+        property.record_resolution(principal, 0)?; // Repaid in full
+    }
+
+    // 6. EXIT: Back to Repaid, with nothing left owed to the liquidator or the bond
+    obligation.is_locked = false;
+    obligation.bounty_owed = 0;
+    obligation.bond_slashed = 0;
+    obligation.reserve_slashed = 0;
+    obligation.default_cured = false;
+    obligation.status = ObligationStatus::Repaid;
+
+    emit!(BookingSettled {
+        obligation: obligation.key(),
+        payer: ctx.accounts.host.key(),
+        principal: split.principal,
+        interest: split.interest,
+        penalty: split.penalty,
+        protocol_fee: split.protocol_fee,
+        reserve_cut: split.reserve_cut,
+        lp_yield: split.lp_yield,
+    });
+
+    msg!("Seized Booking Redeemed by Host: Default Penalty {}", penalty);
+    Ok(())
+}
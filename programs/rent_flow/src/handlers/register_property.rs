@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::constants::{HOST_PROFILE_VERSION, INTEGRATOR_VERSION, MAX_LISTING_ID_LEN, MAX_REGION_LEN, PROPERTY_VERSION};
+use crate::error::ErrorCode;
+use crate::state::{HostProfile, IntegratorConfig, Property};
+
+#[derive(Accounts)]
+#[instruction(listing_id: String, host: Pubkey)]
+pub struct RegisterProperty<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = integration_config.is_active @ ErrorCode::IntegratorNotAuthorized,
+        has_one = authority @ ErrorCode::IntegratorNotAuthorized,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    // One PDA per listing of this integrator
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Property::INIT_SPACE,
+        seeds = [b"property", integration_config.key().as_ref(), listing_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+
+    // Counts the host's listings, so deposit_collateral can refuse their unlinked bookings
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + HostProfile::INIT_SPACE,
+        seeds = [b"host_profile", host.as_ref()],
+        bump
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterProperty>, listing_id: String, host: Pubkey, region: String) -> Result<()> {
+    require!(
+        !listing_id.is_empty() && listing_id.len() <= MAX_LISTING_ID_LEN,
+        ErrorCode::InvalidPropertyDetails
+    );
+    require!(region.len() <= MAX_REGION_LEN, ErrorCode::InvalidPropertyDetails);
+    require_keys_neq!(host, Pubkey::default(), ErrorCode::InvalidPropertyDetails);
+
+    let property = &mut ctx.accounts.property;
+    property.integrator_config = ctx.accounts.integration_config.key();
+    property.listing_id = listing_id;
+    property.host = host;
+    property.region = region;
+    property.registered_at = Clock::get()?.unix_timestamp;
+    property.bump = ctx.bumps.property;
+    property.version = PROPERTY_VERSION;
+
+    let profile = &mut ctx.accounts.host_profile;
+    if profile.host == Pubkey::default() {
+        profile.host = host;
+        profile.bump = ctx.bumps.host_profile;
+        profile.version = HOST_PROFILE_VERSION;
+    }
+    profile.registered_properties = profile.registered_properties.saturating_add(1);
+
+    msg!("Property Registered: {} for host {}", property.listing_id, host);
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::constants::INTEGRATOR_VERSION;
+use crate::state::{IntegratorBond, IntegratorConfig, ProtocolConfig};
+use crate::error::ErrorCode;
+use crate::math::bps_of;
+
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        has_one = authority @ ErrorCode::IntegratorNotAuthorized,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+}
+
+pub fn handler(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let config = &ctx.accounts.protocol_config;
+    let integrator = &ctx.accounts.integration_config;
+    let bond = &mut ctx.accounts.integrator_bond;
+
+    let remaining = bond.amount.checked_sub(amount).ok_or(ErrorCode::InsufficientIntegratorBond)?;
+
+    // 🚩 The remaining bond must still back every loan the integrator originated
+    let required = if integrator.is_active {
+        config.required_bond(integrator.outstanding_principal)?
+    } else {
+        bps_of(integrator.outstanding_principal, config.min_bond_bps as u64)?
+    };
+    require!(remaining >= required, ErrorCode::InsufficientIntegratorBond);
+
+    bond.amount = remaining;
+    bond.pending_unbond = bond.pending_unbond.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    // A fresh request restarts the clock for the whole pending amount
+    bond.unbond_available_at = Clock::get()?.unix_timestamp
+        .checked_add(config.unbonding_period)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Unbond Requested: {} available at {}", bond.pending_unbond, bond.unbond_available_at);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+/*
+  Currency Price Feeds 💱
+
+  Bookings priced in EUR, GBP, ... are converted into the loan currency before
+  LTV applies. Each currency gets one registered feed, quoted in USD (EUR/USD,
+  USDC/USD, ...): a Pyth PriceUpdateV2 account, or a MockPriceFeed on localnet.
+  Registering or replacing a feed is an admin action, like listing a token.
+*/
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_PRICE_CONFIDENCE_BPS, MAX_PRICE_STALENESS};
+use crate::oracle::{read_price_message, validate_currency_code};
+use crate::state::{CurrencyFeed, ProtocolConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(currency: [u8; 3])]
+pub struct SetCurrencyFeed<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CurrencyFeed::INIT_SPACE,
+        seeds = [b"currency_feed", currency.as_ref()],
+        bump
+    )]
+    pub currency_feed: Account<'info, CurrencyFeed>,
+
+    /// CHECK: Owner, layout and feed id are verified by `read_price_message`
+    pub feed: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetCurrencyFeed>,
+    currency: [u8; 3],
+    feed_id: [u8; 32],
+    max_staleness: i64,
+    max_confidence_bps: u16,
+) -> Result<()> {
+    validate_currency_code(&currency)?;
+    require!(
+        (1..=MAX_PRICE_STALENESS).contains(&max_staleness)
+            && (1..=MAX_PRICE_CONFIDENCE_BPS).contains(&max_confidence_bps),
+        ErrorCode::InvalidPriceFeedConfig
+    );
+
+    // The feed must already publish this id; freshness is checked on every use
+    let message = read_price_message(&ctx.accounts.feed.to_account_info())?;
+    require!(message.feed_id == feed_id, ErrorCode::InvalidPriceFeed);
+
+    let currency_feed = &mut ctx.accounts.currency_feed;
+    currency_feed.currency = currency;
+    currency_feed.feed = ctx.accounts.feed.key();
+    currency_feed.feed_id = feed_id;
+    currency_feed.max_staleness = max_staleness;
+    currency_feed.max_confidence_bps = max_confidence_bps;
+    currency_feed.bump = ctx.bumps.currency_feed;
+
+    msg!("Price Feed Set for {}: {}", String::from_utf8_lossy(&currency), currency_feed.feed);
+    Ok(())
+}
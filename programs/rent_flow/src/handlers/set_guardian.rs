@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::state::ProtocolConfig;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.guardian = guardian;
+
+    msg!("Guardian Updated: {}", guardian);
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::constants::INTEGRATOR_VERSION;
+use crate::state::{IntegratorBond, IntegratorConfig, ProtocolConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetIntegratorStatus<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+}
+
+pub fn handler(ctx: Context<SetIntegratorStatus>, is_active: bool) -> Result<()> {
+    if is_active {
+        // 🚩 SKIN IN THE GAME: No bond, no activation
+        let required = ctx.accounts.protocol_config
+            .required_bond(ctx.accounts.integration_config.outstanding_principal)?;
+        let bond = &ctx.accounts.integrator_bond;
+        require!(bond.amount > 0 && bond.amount >= required, ErrorCode::InsufficientIntegratorBond);
+    }
+
+    let config = &mut ctx.accounts.integration_config;
+    config.is_active = is_active;
+
+    msg!("Integrator {} -> active: {}", ctx.accounts.integration_wallet.key(), is_active);
+    Ok(())
+}
@@ -0,0 +1,59 @@
+/*
+  Mock Price Feed 🧪
+
+  Localnet has no Pyth. The admin can publish prices into a MockPriceFeed,
+  which has the PriceUpdateV2 layout and so goes through the same adapter.
+  It is only ever used once registered with set_currency_feed.
+*/
+use anchor_lang::prelude::*;
+use crate::oracle::{PriceFeedMessage, VerificationLevel};
+use crate::state::{MockPriceFeed, ProtocolConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetMockPrice<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + MockPriceFeed::INIT_SPACE,
+        seeds = [b"mock_price_feed", feed_id.as_ref()],
+        bump
+    )]
+    pub mock_price_feed: Account<'info, MockPriceFeed>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetMockPrice>, feed_id: [u8; 32], price: i64, conf: u64, exponent: i32) -> Result<()> {
+    let clock = Clock::get()?;
+    let feed = &mut ctx.accounts.mock_price_feed;
+    let previous = feed.price_message;
+
+    feed.write_authority = ctx.accounts.admin.key();
+    feed.verification_level = VerificationLevel::Full;
+    feed.price_message = PriceFeedMessage {
+        feed_id,
+        price,
+        conf,
+        exponent,
+        publish_time: clock.unix_timestamp,
+        prev_publish_time: previous.publish_time,
+        ema_price: price,
+        ema_conf: conf,
+    };
+    feed.posted_slot = clock.slot;
+
+    msg!("Mock Price Published: {} x 10^{} (+/- {})", price, exponent, conf);
+    Ok(())
+}
@@ -3,8 +3,12 @@ use anchor_spl::{
     token_2022::{self, Token2022},
     token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
-use crate::state::BookingObligation;
+use crate::events::BookingSettled;
+use crate::state::{BookingObligation, HostProfile, IntegratorConfig, ObligationStatus, Property, ProtocolConfig, ReserveFund, SupportedToken};
+use crate::constants::{EARLY_EXIT_PENALTY_BPS, INTEGRATOR_VERSION, OBLIGATION_VERSION, PAUSE_SETTLE, SUPPORTED_TOKEN_VERSION};
 use crate::error::ErrorCode; // Required for custom safety sensors
+use crate::math::bps_of;
+use crate::token_extensions::gross_up;
 
 #[derive(Accounts)]
 pub struct SettleBooking<'info> {
@@ -15,18 +19,85 @@ pub struct SettleBooking<'info> {
         mut,
         seeds = [b"obligation", nft_mint.key().as_ref()],
         bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
         // INVARIANT: Prevent double-settlement and verify ownership
         constraint = !obligation.is_settled @ ErrorCode::AlreadySettled,
+        constraint = obligation.has_active_loan() @ ErrorCode::LoanNotFunded,
         constraint = obligation.host_wallet == host.key() @ ErrorCode::NotHubOwner,
     )]
     pub obligation: Account<'info, BookingObligation>,
 
-    // --- Repayment Corridor ---
-    pub usdc_mint: InterfaceAccount<'info, Mint>, 
+    // --- Repayment Corridor: in the currency that was lent ---
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
-    pub host_usdc_ata: InterfaceAccount<'info, TokenAccount>, // Source of Cash
-    #[account(mut)]
-    pub vault_usdc_ata: InterfaceAccount<'info, TokenAccount>, // Destination (Liquidity Pool)
+    pub host_token_ata: InterfaceAccount<'info, TokenAccount>, // Source of Cash
+
+    // --- Pool Corridor: principal + LP yield ---
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Treasury Corridor: protocol fee ---
+    #[account(
+        mut,
+        associated_token::authority = protocol_config,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Reserve Corridor ---
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_SETTLE) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>, // First-loss cover
+
+    #[account(
+        mut,
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"host_profile", host.key().as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
 
     // --- Asset Release Corridor ---
     pub nft_mint: InterfaceAccount<'info, Mint>,
@@ -35,54 +106,135 @@ pub struct SettleBooking<'info> {
     #[account(mut)]
     pub host_nft_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Interface<'info, TokenInterface>,   // For USDC (Standard SPL)
+    pub token_program: Interface<'info, TokenInterface>,   // For the loan currency
     pub token_2022_program: Program<'info, Token2022>,     // For NFT
     pub clock: Sysvar<'info, Clock>,
 }
-pub fn handler(ctx: Context<SettleBooking>) -> Result<()> {
-    let clock = &ctx.accounts.clock;
-    let obligation = &mut ctx.accounts.obligation;
-    
-    // 📐 THE MATH: Fixed-Point Calculation
-    let principal = obligation.booking_value; // The original USDC disbursed
-    
-    // Assume a fixed 10% Yield for this example (1000 BPS)
-    let yield_bps: u64 = 1000; 
-    let mut total_repayment = principal
-        .checked_add(
-            principal.checked_mul(yield_bps).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10_000).ok_or(ErrorCode::MathOverflow)?
-        ).ok_or(ErrorCode::MathOverflow)?;
+/// How one repayment is split: principal and LP yield to the pool, fee to the
+/// treasury, first-loss slice to the reserve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SettlementSplit {
+    pub principal: u64,
+    pub interest: u64,
+    pub penalty: u64,
+    pub protocol_fee: u64,
+    pub reserve_cut: u64,
+    pub lp_yield: u64,
+}
+
+impl SettlementSplit {
+    /// Splits interest + penalty: fee first, then the reserve slice, the rest is LP yield.
+    pub fn new(principal: u64, interest: u64, penalty: u64, config: &ProtocolConfig) -> Result<Self> {
+        let charges = interest.checked_add(penalty).ok_or(ErrorCode::MathOverflow)?;
+        let protocol_fee = bps_of(charges, config.protocol_fee_bps as u64)?;
+        let reserve_cut = bps_of(charges, config.reserve_bps as u64)?.min(charges - protocol_fee);
+        let lp_yield = charges - protocol_fee - reserve_cut;
+
+        Ok(SettlementSplit { principal, interest, penalty, protocol_fee, reserve_cut, lp_yield })
+    }
+
+    /// Everything the payer owes.
+    pub fn total(&self) -> Result<u64> {
+        self.principal
+            .checked_add(self.interest).ok_or(ErrorCode::MathOverflow)?
+            .checked_add(self.penalty).ok_or(error!(ErrorCode::MathOverflow))
+    }
+
+    /// The pool's share: principal back plus the LPs' yield.
+    pub fn pool_amount(&self) -> Result<u64> {
+        self.principal.checked_add(self.lp_yield).ok_or(error!(ErrorCode::MathOverflow))
+    }
+}
+
+/// Settlement waterfall for `obligation` settled at `now`.
+pub fn settlement_split(obligation: &BookingObligation, config: &ProtocolConfig, now: i64) -> Result<SettlementSplit> {
+    // 📐 THE MATH: Charges accrue on what was actually disbursed
+    let principal = obligation.borrowed_amount;
+    let interest = bps_of(principal, obligation.rate_bps)?;
 
     // 🚩 PENALTY SENSOR: Early Exit Check
-    if clock.unix_timestamp < obligation.end_date {
-        let penalty_bps: u64 = 500; // 5% Penalty
-        let penalty_amount = principal
-            .checked_mul(penalty_bps).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10_000).ok_or(ErrorCode::MathOverflow)?;
-            
-        total_repayment = total_repayment.checked_add(penalty_amount).ok_or(ErrorCode::MathOverflow)?;
-        msg!("Early Exit Detected. 5% Penalty Applied: {} USDC", penalty_amount);
+    let penalty = if now < obligation.end_date {
+        bps_of(principal, EARLY_EXIT_PENALTY_BPS)?
+    } else {
+        0
+    };
+
+    SettlementSplit::new(principal, interest, penalty, config)
+}
+
+pub fn handler(ctx: Context<SettleBooking>) -> Result<()> {
+    let now = ctx.accounts.clock.unix_timestamp;
+    let split = settlement_split(&ctx.accounts.obligation, &ctx.accounts.protocol_config, now)?;
+    if split.penalty > 0 {
+        msg!("Early Exit Detected. 5% Penalty Applied: {}", split.penalty);
     }
+    let decimals = ctx.accounts.loan_mint.decimals;
+    // Transfer-fee mints: the host pays the fee on top, so each leg lands in full
+    let loan_mint = ctx.accounts.loan_mint.to_account_info();
 
-    // --- CPI 1: REPAYMENT (Host -> Vault) ---
-    // Rule: Host must authorize the transfer of USDC to clear the debt.
+    // --- CPI 1: REPAYMENT (Host -> Pool) ---
+    // Rule: Host must authorize the transfer of the loan currency to clear the debt.
     token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             token_interface::TransferChecked {
-                from: ctx.accounts.host_usdc_ata.to_account_info(),
-                mint: ctx.accounts.usdc_mint.to_account_info(),
-                to: ctx.accounts.vault_usdc_ata.to_account_info(),
+                from: ctx.accounts.host_token_ata.to_account_info(),
+                mint: ctx.accounts.loan_mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
                 authority: ctx.accounts.host.to_account_info(),
             },
         ),
-        total_repayment,
-        ctx.accounts.usdc_mint.decimals,
+        gross_up(&loan_mint, split.pool_amount()?)?,
+        decimals,
     )?;
 
+    // --- CPI 1b: PROTOCOL FEE (Host -> Treasury) ---
+    if split.protocol_fee > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.host_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_ata.to_account_info(),
+                    authority: ctx.accounts.host.to_account_info(),
+                },
+            ),
+            gross_up(&loan_mint, split.protocol_fee)?,
+            decimals,
+        )?;
+
+        let config = &mut ctx.accounts.protocol_config;
+        config.total_fees_collected = config.total_fees_collected
+            .checked_add(split.protocol_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    // --- CPI 1c: RESERVE CONTRIBUTION (Host -> Reserve) ---
+    if split.reserve_cut > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.host_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: ctx.accounts.reserve_token_ata.to_account_info(),
+                    authority: ctx.accounts.host.to_account_info(),
+                },
+            ),
+            gross_up(&loan_mint, split.reserve_cut)?,
+            decimals,
+        )?;
+
+        let reserve = &mut ctx.accounts.reserve_fund;
+        reserve.total_contributed = reserve.total_contributed
+            .checked_add(split.reserve_cut)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
     // --- CPI 2: RELEASE (Vault -> Host) ---
     // Rule: Use PDA seeds to release the "Hostage" NFT.
+    let obligation = &mut ctx.accounts.obligation;
     let mint_key = ctx.accounts.nft_mint.key();
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"obligation",
@@ -105,8 +257,88 @@ pub fn handler(ctx: Context<SettleBooking>) -> Result<()> {
     )?;
 
     // --- EXIT: FINAL STATE ---
+    let pool = &mut ctx.accounts.supported_token;
+    pool.outstanding_principal = pool.outstanding_principal
+        .checked_sub(split.principal)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.distribute_yield(split.lp_yield)?;
+
+    let integrator = &mut ctx.accounts.integration_config;
+    integrator.outstanding_principal = integrator.outstanding_principal
+        .checked_sub(split.principal)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // --- REPUTATION: Record how this repayment landed ---
+    ctx.accounts.host_profile.record_repayment(obligation.end_date, now, split.principal, split.total()?)?;
+    if let Some(property) = Property::linked(obligation, &mut ctx.accounts.property)? {
+        property.record_repayment(split.principal)?;
+    }
+
     obligation.is_settled = true;
     obligation.is_locked = false;
+    obligation.status = ObligationStatus::Repaid;
 
+    emit!(BookingSettled {
+        obligation: obligation.key(),
+        payer: ctx.accounts.host.key(),
+        principal: split.principal,
+        interest: split.interest,
+        penalty: split.penalty,
+        protocol_fee: split.protocol_fee,
+        reserve_cut: split.reserve_cut,
+        lp_yield: split.lp_yield,
+    });
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Zeroed accounts (every layout decodes from zeros), then the fields under test.
+    fn zeroed<T: AccountDeserialize + anchor_lang::Space>() -> T {
+        T::try_deserialize_unchecked(&mut vec![0u8; 8 + T::INIT_SPACE].as_slice()).unwrap()
+    }
+
+    fn config(protocol_fee_bps: u16, reserve_bps: u16) -> ProtocolConfig {
+        let mut config = zeroed::<ProtocolConfig>();
+        config.protocol_fee_bps = protocol_fee_bps;
+        config.reserve_bps = reserve_bps;
+        config
+    }
+
+    #[test]
+    fn charges_go_fee_first_then_reserve_then_lps() {
+        let split = SettlementSplit::new(100_000, 8_000, 2_000, &config(1_000, 2_000)).unwrap();
+        assert_eq!(split.protocol_fee, 1_000);
+        assert_eq!(split.reserve_cut, 2_000);
+        assert_eq!(split.lp_yield, 7_000);
+        assert_eq!(split.pool_amount().unwrap(), 107_000);
+        assert_eq!(split.total().unwrap(), 110_000);
+        assert_eq!(split.pool_amount().unwrap() + split.protocol_fee + split.reserve_cut, split.total().unwrap());
+    }
+
+    #[test]
+    fn reserve_cut_never_eats_into_principal() {
+        // Fee and reserve ask for 150% of the charges between them
+        let split = SettlementSplit::new(50_000, 1_000, 0, &config(6_000, 9_000)).unwrap();
+        assert_eq!(split.protocol_fee, 600);
+        assert_eq!(split.reserve_cut, 400);
+        assert_eq!(split.lp_yield, 0);
+        assert_eq!(split.pool_amount().unwrap(), 50_000);
+    }
+
+    #[test]
+    fn early_exit_adds_the_penalty() {
+        let mut obligation = zeroed::<BookingObligation>();
+        obligation.borrowed_amount = 10_000;
+        obligation.rate_bps = 300;
+        obligation.end_date = 1_000;
+
+        let early = settlement_split(&obligation, &config(0, 0), 999).unwrap();
+        assert_eq!((early.interest, early.penalty), (300, 500));
+        let on_time = settlement_split(&obligation, &config(0, 0), 1_000).unwrap();
+        assert_eq!((on_time.interest, on_time.penalty), (300, 0));
+        assert_eq!(on_time.lp_yield, 300);
+    }
+}
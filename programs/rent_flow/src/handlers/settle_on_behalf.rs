@@ -0,0 +1,270 @@
+/*
+  Settle On Behalf 🧾
+
+  The platform payout for a stay lands with the property manager (the integrator),
+  not the host wallet. This lets the integrator authority — or any payer — clear
+  the obligation from that payout:
+
+  - The payer funds the same waterfall as `settle_booking`.
+  - The released NFT still goes to `host_wallet`.
+  - The oracle attests which platform payout covered the loan; the reference is
+    stored on the obligation for reconciliation.
+*/
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{self, Token2022},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+use crate::ed25519::verify_preceding_signature;
+use crate::events::BookingSettled;
+use crate::handlers::settle_booking::settlement_split;
+use crate::state::{BookingObligation, HostProfile, IntegratorConfig, ObligationStatus, Property, ProtocolConfig, ReserveFund, SupportedToken};
+use crate::constants::{INTEGRATOR_VERSION, OBLIGATION_VERSION, PAUSE_SETTLE, SUPPORTED_TOKEN_VERSION};
+use crate::error::ErrorCode;
+use crate::token_extensions::gross_up;
+
+/// Domain tag prefixed to the borsh bytes of a PayoutAttestation before signing.
+pub const PAYOUT_ATTESTATION_DOMAIN: &[u8] = b"rent_flow:payout:v1";
+
+/// What the oracle signs: this platform payout repays this obligation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct PayoutAttestation {
+    pub obligation: Pubkey,
+    pub payout_reference: [u8; 32], // e.g. hash of the platform's payout id
+    pub payout_amount: u64,
+}
+
+impl PayoutAttestation {
+    /// Canonical signed bytes: domain tag || borsh(self).
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = PAYOUT_ATTESTATION_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+#[derive(Accounts)]
+pub struct SettleOnBehalf<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = !obligation.is_settled @ ErrorCode::AlreadySettled,
+        constraint = obligation.has_active_loan() @ ErrorCode::LoanNotFunded,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
+
+    /// CHECK: Only receives the released NFT
+    #[account(address = obligation.host_wallet @ ErrorCode::NotAuthorizedOwner)]
+    pub host_wallet: UncheckedAccount<'info>,
+
+    // --- Repayment Corridor ---
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub payer_token_ata: InterfaceAccount<'info, TokenAccount>, // Source of Cash (platform payout)
+
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_SETTLE) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        associated_token::authority = protocol_config,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"host_profile", obligation.host_wallet.as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
+
+    // --- Asset Release Corridor (Vault -> Host, never the payer) ---
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub vault_nft_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::authority = host_wallet,
+        associated_token::mint = nft_mint,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub host_nft_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Instruction Introspection Sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub sysvar_instructions: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,   // For the loan currency
+    pub token_2022_program: Program<'info, Token2022>,     // For NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SettleOnBehalf>, payout_reference: [u8; 32], payout_amount: u64) -> Result<()> {
+    // 1. ORACLE: The payout reference must be attested for this exact obligation
+    let attestation = PayoutAttestation {
+        obligation: ctx.accounts.obligation.key(),
+        payout_reference,
+        payout_amount,
+    };
+    verify_preceding_signature(
+        &ctx.accounts.sysvar_instructions,
+        &ctx.accounts.protocol_config.oracle,
+        &attestation.message()?,
+    )?;
+
+    // 2. WATERFALL: Same split as a host settlement
+    let now = Clock::get()?.unix_timestamp;
+    let split = settlement_split(&ctx.accounts.obligation, &ctx.accounts.protocol_config, now)?;
+    let decimals = ctx.accounts.loan_mint.decimals;
+
+    let legs = [
+        (ctx.accounts.token_vault.to_account_info(), split.pool_amount()?),
+        (ctx.accounts.treasury_token_ata.to_account_info(), split.protocol_fee),
+        (ctx.accounts.reserve_token_ata.to_account_info(), split.reserve_cut),
+    ];
+    for (destination, amount) in legs {
+        if amount == 0 {
+            continue;
+        }
+        // Transfer-fee mints: the payer covers the fee so each leg lands in full
+        let amount = gross_up(&ctx.accounts.loan_mint.to_account_info(), amount)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.payer_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            amount,
+            decimals,
+        )?;
+    }
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.total_fees_collected = config.total_fees_collected
+        .checked_add(split.protocol_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let reserve = &mut ctx.accounts.reserve_fund;
+    reserve.total_contributed = reserve.total_contributed
+        .checked_add(split.reserve_cut)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // 3. RELEASE: NFT back to the host wallet
+    let obligation = &mut ctx.accounts.obligation;
+    let mint_key = ctx.accounts.nft_mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"obligation",
+        mint_key.as_ref(),
+        &[obligation.bump],
+    ]];
+
+    token_2022::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            token_2022::TransferChecked {
+                from: ctx.accounts.vault_nft_ata.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.host_nft_ata.to_account_info(),
+                authority: obligation.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1, 0
+    )?;
+
+    // 4. EXIT: Books, reputation and the payout trail
+    let pool = &mut ctx.accounts.supported_token;
+    pool.outstanding_principal = pool.outstanding_principal
+        .checked_sub(split.principal)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.distribute_yield(split.lp_yield)?;
+
+    let integrator = &mut ctx.accounts.integration_config;
+    integrator.outstanding_principal = integrator.outstanding_principal
+        .checked_sub(split.principal)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    ctx.accounts.host_profile.record_repayment(obligation.end_date, now, split.principal, split.total()?)?;
+    if let Some(property) = Property::linked(obligation, &mut ctx.accounts.property)? {
+        property.record_repayment(split.principal)?;
+    }
+
+    obligation.is_settled = true;
+    obligation.is_locked = false;
+    obligation.status = ObligationStatus::Repaid;
+    obligation.payout_reference = payout_reference;
+
+    emit!(BookingSettled {
+        obligation: obligation.key(),
+        payer: ctx.accounts.payer.key(),
+        principal: split.principal,
+        interest: split.interest,
+        penalty: split.penalty,
+        protocol_fee: split.protocol_fee,
+        reserve_cut: split.reserve_cut,
+        lp_yield: split.lp_yield,
+    });
+    msg!("Booking Settled On Behalf Of Host By {}", ctx.accounts.payer.key());
+    Ok(())
+}
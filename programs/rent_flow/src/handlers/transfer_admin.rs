@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::ProtocolConfig;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+/// Hands protocol administration to a new key, typically the multisig authority PDA.
+pub fn handler(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.admin = new_admin;
+
+    msg!("Protocol Admin Transferred: {}", new_admin);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::constants::PAUSE_ALL;
+use crate::state::ProtocolConfig;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ ErrorCode::NotProtocolAdmin,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler(ctx: Context<Unpause>, mask: u8) -> Result<()> {
+    require!(mask != 0 && mask & !PAUSE_ALL == 0, ErrorCode::InvalidPauseMask);
+
+    let config = &mut ctx.accounts.protocol_config;
+    config.paused &= !mask;
+
+    msg!("Protocol Unpaused: mask {:#08b} (now {:#08b})", mask, config.paused);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::constants::INTEGRATOR_VERSION;
+use crate::state::{IntegratorBond, IntegratorConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"integrator", integration_wallet.key().as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+        has_one = authority @ ErrorCode::IntegratorNotAuthorized,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    /// CHECK: Seed derivation only
+    pub integration_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator_bond", integration_config.key().as_ref()],
+        bump = integrator_bond.bump,
+        constraint = integrator_bond.mint == usdc_mint.key() @ ErrorCode::MintMismatch,
+    )]
+    pub integrator_bond: Account<'info, IntegratorBond>,
+
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = integrator_bond,
+        associated_token::mint = usdc_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub bond_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<WithdrawBond>) -> Result<()> {
+    let bond = &ctx.accounts.integrator_bond;
+    let amount = bond.pending_unbond;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        Clock::get()?.unix_timestamp >= bond.unbond_available_at,
+        ErrorCode::UnbondingNotReady
+    );
+
+    let config_key = ctx.accounts.integration_config.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"integrator_bond",
+        config_key.as_ref(),
+        &[bond.bump],
+    ]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.bond_usdc_ata.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.authority_usdc_ata.to_account_info(),
+                authority: ctx.accounts.integrator_bond.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    ctx.accounts.integrator_bond.pending_unbond = 0;
+
+    msg!("Integrator Bond Withdrawn: {}", amount);
+    Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount};
 use anchor_spl::token_2022::Token2022;
 use anchor_spl::associated_token::AssociatedToken;
+use crate::constants::OBLIGATION_VERSION;
 use crate::state::BookingObligation;
 use crate::error::ErrorCode;
 
@@ -15,6 +16,7 @@ pub struct WithdrawCollateral<'info> {
         mut,
         seeds = [b"obligation", nft_mint.key().as_ref()],
         bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
         // MUST be settled (Payment verified)
         constraint = obligation.is_settled @ ErrorCode::NotYetSettled,
         // MUST be unlocked (Lien/Rental period finished)
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{Position, PoolVault};
+use crate::constants::{PAUSE_LP_WITHDRAW, POSITION_VERSION, SUPPORTED_TOKEN_VERSION};
+use crate::state::{Position, ProtocolConfig, SupportedToken};
 use crate::error::ErrorCode;
+use crate::token_extensions::net_of_fee;
 
 #[derive(Accounts)]
 pub struct WithdrawLiquidity<'info> {
@@ -10,36 +12,47 @@ pub struct WithdrawLiquidity<'info> {
     pub investor: Signer<'info>,
 
     #[account(
-        init,
-        payer = investor,
-        space = 8 + Position::INIT_SPACE,
-        seeds = [b"position", investor.key().as_ref()],
-        bump
+        mut,
+        close = investor,
+        seeds = [b"position", investor.key().as_ref(), mint.key().as_ref()],
+        bump = position.bump,
+        constraint = position.version == POSITION_VERSION @ ErrorCode::AccountNotMigrated,
     )]
     pub position: Account<'info, Position>,
 
+    // Withdrawals stay open for delisted currencies, so no is_active check
     #[account(
         mut,
-        seeds = [b"pool_vault"],
-        bump = pool_vault.bump,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_LP_WITHDRAW) @ ErrorCode::ProtocolPaused,
     )]
-    pub pool_vault: Account<'info, PoolVault>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
     #[account(
         mut,
         associated_token::authority = investor,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
     )]
-    pub investor_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+    pub investor_token_ata: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        associated_token::authority = pool_vault,
-        associated_token::mint = usdc_mint,
+        associated_token::authority = supported_token,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
     )]
-    pub pool_usdc_ata: InterfaceAccount<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -56,48 +69,62 @@ pub enum InvestmentTerm {
 pub fn handler(ctx: Context<WithdrawLiquidity>) -> Result<()> {
     // [TRANSFORMATION] 1. Extract state to local variables
     // We do this to satisfy the borrow checker and avoid working on a 'closed' account
+    ctx.accounts.position.harvest(&ctx.accounts.supported_token)?; // Also applies written-off losses
     let principal = ctx.accounts.position.principal;
     let realized_profit = ctx.accounts.position.realized_profit;
-    let pool_bump = ctx.accounts.pool_vault.bump;
-    let usdc_decimals = ctx.accounts.usdc_mint.decimals;
+    let vault_bump = ctx.accounts.supported_token.bump;
+    let mint_key = ctx.accounts.mint.key();
+    let decimals = ctx.accounts.mint.decimals;
 
     let total_payout = principal
         .checked_add(realized_profit)
         .ok_or(ErrorCode::MathOverflow)?;
 
-    // [SIGNER SEEDS] 2. The PoolVault PDA must sign the transfer
+    // Funds lent to hosts stay in the pool's books but not in its ATA
+    require!(
+        total_payout <= ctx.accounts.supported_token.available_liquidity(),
+        ErrorCode::InsufficientLiquidity
+    );
+
+    // [SIGNER SEEDS] 2. The SupportedToken PDA owns the vault and must sign the transfer
     let seeds = &[
-        b"pool_vault".as_ref(),
-        &[pool_bump],
+        b"vault".as_ref(),
+        mint_key.as_ref(),
+        &[vault_bump],
     ];
     let signer_seeds = &[&seeds[..]];
 
     // [CPI] 3. Execute the Transfer
-    // Move the fuel (USDC) from the Pool to the Investor's driveway
+    // Move the fuel (tokens) from the currency's vault to the Investor's driveway
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_accounts = TransferChecked {
-        from: ctx.accounts.pool_usdc_ata.to_account_info(),
-        mint: ctx.accounts.usdc_mint.to_account_info(),
-        to: ctx.accounts.investor_usdc_ata.to_account_info(),
-        authority: ctx.accounts.pool_vault.to_account_info(),
+        from: ctx.accounts.token_vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.investor_token_ata.to_account_info(),
+        authority: ctx.accounts.supported_token.to_account_info(),
     };
 
     transfer_checked(
         CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
         total_payout,
-        usdc_decimals,
+        decimals,
     )?;
 
-    // [STATE] 4. Update the Global Pool state
-    // The PoolVault survives this instruction, so we mutate it.
-    let pool_vault = &mut ctx.accounts.pool_vault;
-    pool_vault.total_liquidity_tracked = pool_vault.total_liquidity_tracked
+    // [STATE] 4. Update the currency's pool state
+    // The SupportedToken survives this instruction, so we mutate it.
+    let pool = &mut ctx.accounts.supported_token;
+    pool.total_liquidity_tracked = pool.total_liquidity_tracked
         .checked_sub(total_payout)
         .ok_or(ErrorCode::MathOverflow)?;
+    pool.total_deposits = pool.total_deposits
+        .checked_sub(principal)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     // NOTE: We do NOT need to set position.principal = 0; 
     // The 'close = investor' constraint wipes the account data automatically.
 
-    msg!("Liquidity Withdrawn: {} USDC (Principal + Profit Share)", total_payout);
+    // Outbound transfer fees are the investor's to bear
+    let received = net_of_fee(&ctx.accounts.mint.to_account_info(), total_payout)?;
+    msg!("Liquidity Withdrawn: {} of {} (Principal + Profit Share), {} received", total_payout, mint_key, received);
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,121 @@
+/*
+  Withdraw Repayment Escrow 💸
+
+  crank_settle is the only path that spends the escrow. When the loan closes any
+  other way (the host settles directly or on behalf, or the loan is liquidated
+  because the escrow fell short) the escrowed cash would be stranded under the
+  obligation PDA. Once the loan is no longer Active the host takes it back here,
+  and the emptied escrow's rent with it; the integrator's share of the escrow
+  goes back to the integrator.
+*/
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::state::{BookingObligation, IntegratorConfig, ObligationStatus};
+use crate::constants::{INTEGRATOR_VERSION, OBLIGATION_VERSION};
+use crate::error::ErrorCode;
+use crate::token_extensions::withheld_fees;
+
+#[derive(Accounts)]
+pub struct WithdrawRepaymentEscrow<'info> {
+    #[account(mut)]
+    pub host: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"obligation", nft_mint.key().as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = obligation.host_wallet == host.key() @ ErrorCode::NotAuthorizedOwner,
+        // Repaid, seized or resolved: the escrow can no longer be cranked
+        constraint = obligation.status != ObligationStatus::Active @ ErrorCode::EscrowStillCommitted,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = obligation,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = loan_mint,
+        token::authority = host,
+    )]
+    pub host_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+    // Required when the integrator funded part of the escrow
+    #[account(
+        mut,
+        token::mint = loan_mint,
+        token::authority = integration_config.authority,
+    )]
+    pub integrator_token_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<WithdrawRepaymentEscrow>) -> Result<()> {
+    let mint_key = ctx.accounts.nft_mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"obligation", mint_key.as_ref(), &[ctx.accounts.obligation.bump]]];
+
+    // 1. REFUND: Everything escrowed goes back to whoever funded it (each bears any transfer fee)
+    let escrowed = ctx.accounts.escrow_token_ata.amount;
+    let integrator_refund = ctx.accounts.obligation.integrator_escrow_share(escrowed, escrowed)?;
+    let host_refund = escrowed - integrator_refund;
+
+    let mut legs = vec![(ctx.accounts.host_token_ata.to_account_info(), host_refund)];
+    if integrator_refund > 0 {
+        let integrator_token_ata = ctx.accounts.integrator_token_ata.as_ref()
+            .ok_or(ErrorCode::MissingIntegratorTokenAccount)?;
+        legs.push((integrator_token_ata.to_account_info(), integrator_refund));
+    }
+    for (destination, amount) in legs {
+        if amount == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.escrow_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.obligation.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.loan_mint.decimals,
+        )?;
+    }
+    ctx.accounts.obligation.integrator_escrowed = 0;
+
+    // 2. CLOSE: Rent back to the host, unless withheld transfer fees still pin the account
+    if withheld_fees(&ctx.accounts.escrow_token_ata.to_account_info())? == 0 {
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.escrow_token_ata.to_account_info(),
+                destination: ctx.accounts.host.to_account_info(),
+                authority: ctx.accounts.obligation.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    msg!("Repayment Escrow Withdrawn: {} back to the host, {} to the integrator", host_refund, integrator_refund);
+    Ok(())
+}
@@ -0,0 +1,244 @@
+/*
+  Bad-Debt Write-Off 🧾
+
+  The Problem: A seized booking nobody buys keeps its debt on the pool's books
+  forever. LPs see a share value that the pool can never pay out.
+
+  The Solution: Write the claim off. The integrator's slashed bond already sits
+  in the pool; the reserve fund covers what it can of the rest, and only the
+  remainder is realized as a loss, shrinking every LP position pro-rata.
+
+  The admin can write off any defaulted obligation once the host's redemption
+  window has closed; once WRITE_OFF_DELAY has passed since bidding opened, so can anyone.
+
+  A liquidation bounty still owed is paid now from idle pool cash and written off
+  with the claim. The seized NFT stays on sale: whatever it fetches later is paid
+  to the liquidator first (if the pool could not pay them), then back to the LPs.
+*/
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::constants::{INTEGRATOR_VERSION, OBLIGATION_VERSION, PAUSE_LIQUIDATE, SUPPORTED_TOKEN_VERSION, WRITE_OFF_DELAY};
+use crate::state::{BookingObligation, HostProfile, IntegratorConfig, ObligationStatus, Property, ProtocolConfig, ReserveFund, SupportedToken};
+use crate::error::ErrorCode;
+use crate::token_extensions::net_of_fee;
+use crate::events::LossRealized;
+
+#[derive(Accounts)]
+pub struct WriteOff<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"obligation", obligation.nft_mint.as_ref()],
+        bump = obligation.bump,
+        constraint = obligation.version == OBLIGATION_VERSION @ ErrorCode::AccountNotMigrated,
+        constraint = obligation.status == ObligationStatus::Defaulted @ ErrorCode::NotDefaulted,
+    )]
+    pub obligation: Account<'info, BookingObligation>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(PAUSE_LIQUIDATE) @ ErrorCode::ProtocolPaused,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    // The lending pool of the loan's currency
+    #[account(
+        mut,
+        seeds = [b"vault", loan_mint.key().as_ref()],
+        bump = supported_token.bump,
+        constraint = supported_token.version == SUPPORTED_TOKEN_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub supported_token: Account<'info, SupportedToken>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve_fund"],
+        bump = reserve_fund.bump,
+    )]
+    pub reserve_fund: Account<'info, ReserveFund>,
+
+    #[account(
+        mut,
+        seeds = [b"integrator", obligation.integrator_wallet.as_ref()],
+        bump = integration_config.bump,
+        constraint = integration_config.version == INTEGRATOR_VERSION @ ErrorCode::AccountNotMigrated,
+    )]
+    pub integration_config: Account<'info, IntegratorConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"host_profile", obligation.host_wallet.as_ref()],
+        bump = host_profile.bump,
+    )]
+    pub host_profile: Account<'info, HostProfile>,
+
+    // --- Property: required when the obligation is linked to one ---
+    #[account(mut, address = obligation.property @ ErrorCode::PropertyMismatch)]
+    pub property: Option<Account<'info, Property>>,
+
+    #[account(address = obligation.loan_mint @ ErrorCode::MintMismatch)]
+    pub loan_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = supported_token,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = reserve_fund,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub reserve_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // --- Bounty Corridor (Pool -> Liquidator, deferred bounties only) ---
+    /// CHECK: Only receives the deferred bounty
+    #[account(address = obligation.liquidator)]
+    pub liquidator: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::authority = liquidator,
+        associated_token::mint = loan_mint,
+        associated_token::token_program = token_program,
+    )]
+    pub liquidator_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<WriteOff>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let obligation = &ctx.accounts.obligation;
+
+    // 1. GATE: After the redemption window; admin at any time, anyone once the auction has clearly failed
+    require!(now > obligation.redeem_deadline, ErrorCode::RedemptionWindowOpen);
+    let due_at = obligation.redeem_deadline
+        .checked_add(WRITE_OFF_DELAY)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.protocol_config.admin || now >= due_at,
+        ErrorCode::WriteOffNotDue
+    );
+
+    // 2. DEFERRED BOUNTY: Settled from idle pool cash, as it would have been at seizure
+    let bounty_owed = obligation.bounty_owed;
+    let bounty_settled = if bounty_owed > 0
+        && bounty_owed <= ctx.accounts.supported_token.available_liquidity()
+        && bounty_owed <= ctx.accounts.token_vault.amount
+    {
+        let loan_mint_key = ctx.accounts.loan_mint.key();
+        let pool_seeds: &[&[&[u8]]] = &[&[b"vault", loan_mint_key.as_ref(), &[ctx.accounts.supported_token.bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: ctx.accounts.liquidator_token_ata.to_account_info(),
+                    authority: ctx.accounts.supported_token.to_account_info(),
+                },
+                pool_seeds,
+            ),
+            bounty_owed,
+            ctx.accounts.loan_mint.decimals,
+        )?;
+        bounty_owed
+    } else {
+        0 // Stays owed, paid first out of any later sale of the NFT
+    };
+
+    // 3. THE CLAIM: Debt + bounties the pool paid, less the bond already slashed into the pool
+    let obligation = &ctx.accounts.obligation;
+    let debt = obligation.borrowed_amount;
+    let booked_claim = debt
+        .checked_add(obligation.bounty_paid)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let pool_claim = booked_claim
+        .checked_add(bounty_settled)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let shortfall = pool_claim.saturating_sub(obligation.bond_slashed);
+
+    // 4. FIRST-LOSS COVER: The reserve pays before LPs do
+    let cover = shortfall.min(ctx.accounts.reserve_token_ata.amount);
+    if cover > 0 {
+        let reserve_seeds: &[&[&[u8]]] = &[&[b"reserve_fund", &[ctx.accounts.reserve_fund.bump]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.reserve_token_ata.to_account_info(),
+                    mint: ctx.accounts.loan_mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.reserve_fund.to_account_info(),
+                },
+                reserve_seeds,
+            ),
+            cover,
+            ctx.accounts.loan_mint.decimals,
+        )?;
+
+        let reserve = &mut ctx.accounts.reserve_fund;
+        reserve.total_covered = reserve.total_covered
+            .checked_add(cover)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    // Only what lands in the vault reduces the LPs' loss
+    let cover = net_of_fee(&ctx.accounts.loan_mint.to_account_info(), cover)?;
+
+    // 5. SOCIALIZE: The loan leaves the books, the rest comes out of LP principal
+    let lp_loss = shortfall - cover;
+    let pool = &mut ctx.accounts.supported_token;
+    pool.outstanding_principal = pool.outstanding_principal
+        .checked_sub(booked_claim)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.realize_loss(lp_loss)?;
+
+    let integrator = &mut ctx.accounts.integration_config;
+    integrator.outstanding_principal = integrator.outstanding_principal
+        .checked_sub(debt)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let profile = &mut ctx.accounts.host_profile;
+    profile.outstanding_principal = profile.outstanding_principal
+        .checked_sub(debt)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if let Some(property) = Property::linked(&ctx.accounts.obligation, &mut ctx.accounts.property)? {
+        property.record_resolution(debt, shortfall)?;
+    }
+
+    let obligation = &mut ctx.accounts.obligation;
+    obligation.reserve_covered = cover;
+    obligation.bounty_paid = obligation.bounty_paid
+        .checked_add(bounty_settled)
+        .ok_or(ErrorCode::MathOverflow)?;
+    obligation.bounty_owed = bounty_owed - bounty_settled;
+    obligation.status = ObligationStatus::WrittenOff;
+
+    emit!(LossRealized {
+        obligation: obligation.key(),
+        claim: pool_claim,
+        recovered: 0,
+        bond_slashed: obligation.bond_slashed,
+        reserve_covered: cover,
+        lp_loss,
+    });
+
+    msg!(
+        "Bad Debt Written Off: Claim {}, Reserve Cover: {}, LP Loss: {}, Bounty Settled: {}, Still Owed: {}",
+        pool_claim, cover, lp_loss, bounty_settled, obligation.bounty_owed
+    );
+    Ok(())
+}
@@ -3,13 +3,22 @@ use anchor_lang::prelude::*;
 declare_id!("29u6Rxe7tsrWFoUifHfvyYMqn3n9CBe5BmzuiPLk3CEJ");
 
 pub mod constants;
+pub mod ed25519;
 pub mod error;
+pub mod events;
 pub mod handlers;
+pub mod math;
+pub mod merkle;
+pub mod migration;
+pub mod oracle;
+pub mod proof;
 pub mod state;
+pub mod token_extensions;
 
 #[allow(ambiguous_glob_reexports)]
 pub use handlers::*;
 pub use state::*;
+pub use proof::{BookingProof, BookingProofEnvelope};
 
 #[program]
 pub mod rent_flow {
@@ -19,14 +28,67 @@ pub mod rent_flow {
         handlers::initialize::handler(ctx)
     }
 
-    pub fn add_token(ctx: Context<InitializeSupportedToken>, ltv_bps: u16) -> Result<()> {
-        handlers::init_vault::handler(ctx, ltv_bps)
+    pub fn add_token(ctx: Context<InitializeSupportedToken>, ltv_bps: u16, currency: [u8; 3]) -> Result<()> {
+        handlers::init_vault::handler(ctx, ltv_bps, currency)
     }
 
-    pub fn mint_booking(ctx: Context<MintBooking>, booking_data: BookingProof) -> Result<()> {
+    pub fn set_currency_feed(
+        ctx: Context<SetCurrencyFeed>,
+        currency: [u8; 3],
+        feed_id: [u8; 32],
+        max_staleness: i64,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        handlers::set_currency_feed::handler(ctx, currency, feed_id, max_staleness, max_confidence_bps)
+    }
+
+    pub fn set_mock_price(ctx: Context<SetMockPrice>, feed_id: [u8; 32], price: i64, conf: u64, exponent: i32) -> Result<()> {
+        handlers::set_mock_price::handler(ctx, feed_id, price, conf, exponent)
+    }
+
+    pub fn mint_booking(ctx: Context<MintBooking>, booking_data: BookingProofEnvelope) -> Result<()> {
         handlers::mint_booking::handler(ctx, booking_data)
     }
 
+    pub fn mint_bookings_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintBookingsBatch<'info>>,
+        bookings: Vec<BookingProofEnvelope>,
+    ) -> Result<()> {
+        handlers::mint_bookings_batch::handler(ctx, bookings)
+    }
+
+    pub fn post_booking_root(
+        ctx: Context<PostBookingRoot>,
+        batch_id: u64,
+        root: [u8; 32],
+        leaf_count: u32,
+        expires_at: i64,
+    ) -> Result<()> {
+        handlers::post_booking_root::handler(ctx, batch_id, root, leaf_count, expires_at)
+    }
+
+    pub fn mint_booking_from_root(
+        ctx: Context<MintBookingFromRoot>,
+        booking_data: BookingProofEnvelope,
+        leaf_index: u32,
+        merkle_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        handlers::mint_booking_from_root::handler(ctx, booking_data, leaf_index, merkle_proof)
+    }
+
+    pub fn close_booking_batch(ctx: Context<CloseBookingBatch>) -> Result<()> {
+        handlers::close_booking_batch::handler(ctx)
+    }
+
+    pub fn register_property(
+        ctx: Context<RegisterProperty>,
+        listing_id: String,
+        host: Pubkey,
+        region: String,
+    ) -> Result<()> {
+        handlers::register_property::handler(ctx, listing_id, host, region)
+    }
+
     pub fn deposit_collateral(ctx: Context<DepositCollateral>, funding_amount: u64) -> Result<()> {
         handlers::deposit_collateral::handler(ctx, funding_amount)
     }
@@ -35,6 +97,30 @@ pub mod rent_flow {
         handlers::settle_booking::handler(ctx)
     }
 
+    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+        handlers::deposit_liquidity::handler(ctx, amount)
+    }
+
+    pub fn settle_on_behalf(
+        ctx: Context<SettleOnBehalf>,
+        payout_reference: [u8; 32],
+        payout_amount: u64,
+    ) -> Result<()> {
+        handlers::settle_on_behalf::handler(ctx, payout_reference, payout_amount)
+    }
+
+    pub fn fund_repayment_escrow(ctx: Context<FundRepaymentEscrow>, amount: u64) -> Result<()> {
+        handlers::fund_repayment_escrow::handler(ctx, amount)
+    }
+
+    pub fn crank_settle(ctx: Context<CrankSettle>) -> Result<()> {
+        handlers::crank_settle::handler(ctx)
+    }
+
+    pub fn withdraw_repayment_escrow(ctx: Context<WithdrawRepaymentEscrow>) -> Result<()> {
+        handlers::withdraw_repayment_escrow::handler(ctx)
+    }
+
     pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>) -> Result<()> {
         handlers::withdraw_liquidity::handler(ctx)
     }
@@ -42,4 +128,118 @@ pub mod rent_flow {
     pub fn liquidate_default(ctx: Context<LiquidateDefault>) -> Result<()> {
         handlers::liquidate_default::handler(ctx)
     }
+
+    pub fn initialize_protocol(ctx: Context<InitializeProtocol>, reserve_bps: u16) -> Result<()> {
+        handlers::init_protocol::handler(ctx, reserve_bps)
+    }
+
+    pub fn purchase_seized_collateral(ctx: Context<PurchaseSeizedCollateral>, max_price: u64) -> Result<()> {
+        handlers::liquidated_asset_auction::handler(ctx, max_price)
+    }
+
+    pub fn write_off(ctx: Context<WriteOff>) -> Result<()> {
+        handlers::write_off::handler(ctx)
+    }
+
+    pub fn redeem_defaulted(ctx: Context<RedeemDefaulted>) -> Result<()> {
+        handlers::redeem_defaulted::handler(ctx)
+    }
+
+    pub fn pool_stats(ctx: Context<GetPoolStats>) -> Result<PoolStats> {
+        handlers::pool_stats::handler(ctx)
+    }
+
+    pub fn post_integrator_bond(ctx: Context<PostIntegratorBond>, amount: u64) -> Result<()> {
+        handlers::post_integrator_bond::handler(ctx, amount)
+    }
+
+    pub fn set_integrator_status(ctx: Context<SetIntegratorStatus>, is_active: bool) -> Result<()> {
+        handlers::set_integrator_status::handler(ctx, is_active)
+    }
+
+    pub fn request_unbond(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+        handlers::request_unbond::handler(ctx, amount)
+    }
+
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+        handlers::withdraw_bond::handler(ctx)
+    }
+
+    pub fn cure_host_default(ctx: Context<CureHostDefault>) -> Result<()> {
+        handlers::cure_host_default::handler(ctx)
+    }
+
+    pub fn pause(ctx: Context<Pause>, mask: u8) -> Result<()> {
+        handlers::pause::handler(ctx, mask)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, mask: u8) -> Result<()> {
+        handlers::unpause::handler(ctx, mask)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        handlers::set_guardian::handler(ctx, guardian)
+    }
+
+    pub fn queue_change(ctx: Context<QueueChange>, change: ParamChange) -> Result<()> {
+        handlers::queue_change::handler(ctx, change)
+    }
+
+    pub fn execute_change(ctx: Context<ExecuteChange>) -> Result<()> {
+        handlers::execute_change::handler(ctx)
+    }
+
+    pub fn cancel_change(ctx: Context<CancelChange>) -> Result<()> {
+        handlers::cancel_change::handler(ctx)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        handlers::transfer_admin::handler(ctx, new_admin)
+    }
+
+    pub fn create_multisig(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        handlers::create_multisig::handler(ctx, signers, threshold)
+    }
+
+    pub fn propose_admin_action(
+        ctx: Context<ProposeAdminAction>,
+        action: AdminAction,
+        accounts: Vec<ProposalAccount>,
+    ) -> Result<()> {
+        handlers::propose_admin_action::handler(ctx, action, accounts)
+    }
+
+    pub fn approve_admin_proposal(ctx: Context<ApproveAdminProposal>) -> Result<()> {
+        handlers::approve_admin_proposal::handler(ctx)
+    }
+
+    pub fn execute_admin_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteAdminProposal<'info>>,
+    ) -> Result<()> {
+        handlers::execute_admin_proposal::handler(ctx)
+    }
+
+    pub fn migrate_obligation(ctx: Context<MigrateObligation>) -> Result<()> {
+        handlers::migrate_obligation::handler(ctx)
+    }
+
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        handlers::migrate_position::handler(ctx)
+    }
+
+    pub fn migrate_pool_vault(ctx: Context<MigratePoolVault>) -> Result<()> {
+        handlers::migrate_pool_vault::handler(ctx)
+    }
+
+    pub fn migrate_supported_token(ctx: Context<MigrateSupportedToken>) -> Result<()> {
+        handlers::migrate_supported_token::handler(ctx)
+    }
+
+    pub fn migrate_integrator(ctx: Context<MigrateIntegrator>) -> Result<()> {
+        handlers::migrate_integrator::handler(ctx)
+    }
+
+    pub fn migrate_pool_liquidity(ctx: Context<MigratePoolLiquidity>) -> Result<()> {
+        handlers::migrate_pool_liquidity::handler(ctx)
+    }
 }
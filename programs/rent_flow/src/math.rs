@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+use crate::error::ErrorCode;
+
+/// Returns `amount * bps / 10_000`, computed in u128 so large principals cannot overflow.
+pub fn bps_of(amount: u64, bps: u64) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128).ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128).ok_or(ErrorCode::MathOverflow)?;
+    u64::try_from(value).map_err(|_| error!(ErrorCode::MathOverflow))
+}
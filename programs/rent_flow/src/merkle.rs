@@ -0,0 +1,130 @@
+//! Merkle trees of booking proofs, for roots the oracle signs once per batch.
+//!
+//! Leaves are `sha256(0x00 || signed_message)` of each `BookingProofEnvelope`, in
+//! leaf-index order, padded with zeroed leaves to the next power of two. Nodes are
+//! `sha256(0x01 || left || right)`. Proofs are positional: the leaf index picks the
+//! side at each level, so a leaf can only be claimed at its own index.
+use anchor_lang::prelude::*;
+use spl_token_2022::solana_program::hash::hashv;
+use crate::error::ErrorCode;
+
+/// Domain separators, so a leaf can never pass for an inner node.
+pub const LEAF_PREFIX: u8 = 0x00;
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// Leaf hash of one proof's signed bytes.
+pub fn leaf_hash(message: &[u8]) -> [u8; 32] {
+    hashv(&[&[LEAF_PREFIX], message]).to_bytes()
+}
+
+/// Inner node over two children.
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[NODE_PREFIX], left, right]).to_bytes()
+}
+
+/// Levels in a tree of `leaf_count` leaves (0 for a single leaf).
+pub fn tree_depth(leaf_count: u32) -> usize {
+    leaf_count.max(1).next_power_of_two().trailing_zeros() as usize
+}
+
+/// Checks that `leaf` sits at `index` of the `leaf_count`-leaf tree under `root`.
+pub fn verify_proof(root: &[u8; 32], leaf_count: u32, leaf: [u8; 32], index: u32, proof: &[[u8; 32]]) -> Result<()> {
+    require!(index < leaf_count, ErrorCode::InvalidMerkleProof);
+    require!(proof.len() == tree_depth(leaf_count), ErrorCode::InvalidMerkleProof);
+
+    let mut node = leaf;
+    for (level, sibling) in proof.iter().enumerate() {
+        node = if (index >> level) & 1 == 0 {
+            node_hash(&node, sibling)
+        } else {
+            node_hash(sibling, &node)
+        };
+    }
+    require!(node == *root, ErrorCode::InvalidMerkleProof);
+    Ok(())
+}
+
+/// Root over `leaves` (padded as above), for integrators and oracles building a batch.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let width = leaves.len().max(1).next_power_of_two();
+    let mut level: Vec<[u8; 32]> = leaves.to_vec();
+    level.resize(width, [0u8; 32]);
+    while level.len() > 1 {
+        level = level.chunks(2).map(|pair| node_hash(&pair[0], &pair[1])).collect();
+    }
+    level[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| leaf_hash(&[i; 40])).collect()
+    }
+
+    /// Sibling path of `index` in the padded tree over `leaves`.
+    fn proof_for(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+        let mut level = leaves.to_vec();
+        level.resize(leaves.len().max(1).next_power_of_two(), [0u8; 32]);
+        let mut proof = Vec::new();
+        while level.len() > 1 {
+            proof.push(level[index ^ 1]);
+            level = level.chunks(2).map(|pair| node_hash(&pair[0], &pair[1])).collect();
+            index /= 2;
+        }
+        proof
+    }
+
+    #[test]
+    fn every_leaf_verifies_at_its_own_index() {
+        for count in [1u8, 2, 3, 5, 8] {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = proof_for(&leaves, index);
+                assert!(verify_proof(&root, count as u32, *leaf, index as u32, &proof).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn leaf_at_the_wrong_index_is_rejected() {
+        let leaves = leaves(4);
+        let root = merkle_root(&leaves);
+        let proof = proof_for(&leaves, 1);
+        assert!(verify_proof(&root, 4, leaves[1], 2, &proof).is_err());
+        assert!(verify_proof(&root, 4, leaves[1], 0, &proof).is_err());
+    }
+
+    #[test]
+    fn padding_leaves_cannot_be_claimed() {
+        let leaves = leaves(3);
+        let root = merkle_root(&leaves);
+        let proof = proof_for(&leaves, 3);
+        // The path to the zeroed leaf is genuine, but it lies past the signed leaf_count
+        assert!(verify_proof(&root, 3, [0u8; 32], 3, &proof).is_err());
+    }
+
+    #[test]
+    fn proof_of_the_wrong_depth_is_rejected() {
+        let leaves = leaves(4);
+        let root = merkle_root(&leaves);
+        let mut proof = proof_for(&leaves, 0);
+        proof.push([0u8; 32]);
+        assert!(verify_proof(&root, 4, leaves[0], 0, &proof).is_err());
+        assert!(verify_proof(&root, 4, leaves[0], 0, &proof[..1]).is_err());
+    }
+
+    #[test]
+    fn inner_node_cannot_pass_for_a_leaf() {
+        let leaves = leaves(4);
+        let root = merkle_root(&leaves);
+        let node = node_hash(&leaves[0], &leaves[1]);
+        let proof = proof_for(&leaves, 0);
+        // Its path is one level short of the depth leaf_count pins
+        assert!(verify_proof(&root, 4, node, 0, &proof[1..]).is_err());
+        // And prefixes keep a leaf's hash from ever equalling a node over the same bytes
+        assert_ne!(leaf_hash(&[leaves[0], leaves[1]].concat()), node);
+    }
+}
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::Versioned;
+use crate::error::ErrorCode;
+
+/// Decodes `account` as the current layout of `T`, whatever version it is stored in.
+pub fn decode_stored<T>(account: &AccountInfo, space: usize) -> Result<T>
+where
+    T: AccountDeserialize + Discriminator + Versioned,
+{
+    decode_data(&account.try_borrow_data()?, space)
+}
+
+/// Decodes raw account bytes (discriminator included) as the current layout of `T`.
+///
+/// Versioned layouts only ever grow at the tail (new fields are carved out of
+/// `_reserved`, or appended once it runs out), so any of them decodes as `T` once
+/// zero-padded to `space`. Accounts written before versioning read as version 0; where
+/// the baseline layout is not a prefix of the current one, `T::decode_baseline` reads it.
+pub fn decode_data<T>(data: &[u8], space: usize) -> Result<T>
+where
+    T: AccountDeserialize + Discriminator + Versioned,
+{
+    require!(
+        data.len() >= 8 && data[..8] == *T::DISCRIMINATOR,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    if let Some(baseline) = T::decode_baseline(&data[8..]) {
+        return baseline;
+    }
+
+    let mut padded = data.to_vec();
+    if padded.len() < space {
+        padded.resize(space, 0);
+    }
+    T::try_deserialize(&mut padded.as_slice())
+}
+
+/// Rewrites `account` in the current layout of `T`, reallocating to `space` first.
+///
+/// `upgrade` fills non-zero defaults. Any rent needed for the extra bytes is paid by `payer`.
+pub fn migrate<'info, T>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<T>
+where
+    T: AccountSerialize + AccountDeserialize + Discriminator + Versioned,
+{
+    migrate_with(account, payer, system_program, space, |_| Ok(()))
+}
+
+/// `migrate`, with `fill` setting defaults that need more context than `upgrade` has.
+pub fn migrate_with<'info, T>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    fill: impl FnOnce(&mut T) -> Result<()>,
+) -> Result<T>
+where
+    T: AccountSerialize + AccountDeserialize + Discriminator + Versioned,
+{
+    // 1. DECODE: Stored bytes, in whichever layout they were written
+    let mut value = decode_stored::<T>(account, space)?;
+    require!(value.version() < T::VERSION, ErrorCode::AlreadyMigrated);
+    value.upgrade();
+    fill(&mut value)?;
+
+    // 2. REALLOC: Top up rent, then grow to the new layout
+    if account.data_len() < space {
+        let required = Rent::get()?.minimum_balance(space);
+        let shortfall = required.saturating_sub(account.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer { from: payer.clone(), to: account.clone() },
+                ),
+                shortfall,
+            )?;
+        }
+        account.resize(space)?;
+    }
+
+    // 3. WRITE: Discriminator + new layout
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    value.try_serialize(&mut writer)?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{ACCOUNT_RESERVED_BYTES, DEFAULT_GRACE_PERIOD, OBLIGATION_VERSION, POOL_VAULT_VERSION};
+    use crate::state::{
+        BookingObligation, LegacyBookingObligation, LegacyPoolVault, ObligationStatus, PoolVault, ProfitTier,
+    };
+
+    /// Account bytes as the runtime holds them: discriminator, borsh body, zeroed to `space`.
+    fn account_bytes<T: AnchorSerialize>(discriminator: &[u8], value: &T, space: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        value.serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        data
+    }
+
+    fn baseline_obligation(is_settled: bool) -> LegacyBookingObligation {
+        LegacyBookingObligation {
+            booking_id: "HM-4821".to_string(),
+            booking_value: 1_000_000,
+            max_principal: 750_000,
+            borrowed_amount: 500_000,
+            start_date: 1_700_000_000,
+            end_date: 1_702_592_000,
+            host_wallet: Pubkey::new_unique(),
+            integrator_wallet: Pubkey::new_unique(),
+            nft_mint: Pubkey::new_unique(),
+            is_locked: !is_settled,
+            is_settled,
+            tier: ProfitTier::SixMonth,
+            bump: 253,
+        }
+    }
+
+    #[test]
+    fn baseline_obligation_decodes_through_its_own_layout() {
+        let legacy = baseline_obligation(false);
+        let (host, mint) = (legacy.host_wallet, legacy.nft_mint);
+        let data = account_bytes(
+            BookingObligation::DISCRIMINATOR,
+            &legacy,
+            8 + LegacyBookingObligation::INIT_SPACE,
+        );
+
+        let mut decoded = decode_data::<BookingObligation>(&data, 8 + BookingObligation::INIT_SPACE).unwrap();
+        assert_eq!(decoded.booking_id, "HM-4821");
+        assert_eq!(decoded.borrowed_amount, 500_000);
+        assert_eq!(decoded.end_date, 1_702_592_000);
+        assert_eq!(decoded.host_wallet, host);
+        assert_eq!(decoded.nft_mint, mint);
+        assert!(decoded.is_locked);
+        assert_eq!(decoded.tier, ProfitTier::SixMonth);
+        assert_eq!(decoded.bump, 253);
+        assert_eq!(decoded.status, ObligationStatus::Active);
+        assert_eq!(decoded.version, 0);
+
+        decoded.upgrade();
+        assert_eq!(decoded.version, OBLIGATION_VERSION);
+        assert_eq!(decoded.grace_period, DEFAULT_GRACE_PERIOD);
+    }
+
+    #[test]
+    fn settled_baseline_obligation_reads_as_repaid() {
+        let data = account_bytes(
+            BookingObligation::DISCRIMINATOR,
+            &baseline_obligation(true),
+            8 + LegacyBookingObligation::INIT_SPACE,
+        );
+        let decoded = decode_data::<BookingObligation>(&data, 8 + BookingObligation::INIT_SPACE).unwrap();
+        assert_eq!(decoded.status, ObligationStatus::Repaid);
+    }
+
+    #[test]
+    fn versioned_obligation_decodes_zero_padded() {
+        // A v1 account: the current layout up to `version`, then the original reserve
+        let mut v1 = BookingObligation::from(baseline_obligation(false));
+        v1.version = 1;
+        v1.rate_bps = 900;
+        let mut full = BookingObligation::DISCRIMINATOR.to_vec();
+        v1.serialize(&mut full).unwrap();
+        // discriminator, booking_id, amounts + dates, wallets + mint, flags + tier + status,
+        // default / pricing fields, bump + version
+        let body = 8 + 4 + v1.booking_id.len() + 5 * 8 + 3 * 32 + 4 + 5 * 8 + 2;
+        let data = full[..body + ACCOUNT_RESERVED_BYTES].to_vec();
+
+        let decoded = decode_data::<BookingObligation>(&data, 8 + BookingObligation::INIT_SPACE).unwrap();
+        assert_eq!(decoded.version, 1);
+        assert_eq!(decoded.rate_bps, 900);
+        assert_eq!(decoded.bump, 253);
+        assert_eq!(decoded.tier, ProfitTier::SixMonth);
+        assert_eq!(decoded.property, Pubkey::default());
+    }
+
+    #[test]
+    fn baseline_pool_vault_decodes_through_its_own_layout() {
+        let legacy = LegacyPoolVault { total_liquidity_tracked: 42_000_000, bump: 254 };
+        let data = account_bytes(PoolVault::DISCRIMINATOR, &legacy, 8 + LegacyPoolVault::INIT_SPACE);
+
+        let mut decoded = decode_data::<PoolVault>(&data, 8 + PoolVault::INIT_SPACE).unwrap();
+        assert_eq!(decoded.total_liquidity_tracked, 42_000_000);
+        assert_eq!(decoded.outstanding_principal, 0);
+        assert_eq!(decoded.bump, 254);
+        assert!(decoded.rate_model.base_rate_bps > 0);
+
+        decoded.upgrade();
+        assert_eq!(decoded.version, POOL_VAULT_VERSION);
+        assert_eq!(decoded.total_deposits, 42_000_000); // Every tracked dollar was LP principal
+    }
+
+    #[test]
+    fn rejects_another_account_type() {
+        let data = account_bytes(
+            PoolVault::DISCRIMINATOR,
+            &baseline_obligation(false),
+            8 + LegacyBookingObligation::INIT_SPACE,
+        );
+        assert!(decode_data::<BookingObligation>(&data, 8 + BookingObligation::INIT_SPACE).is_err());
+    }
+}
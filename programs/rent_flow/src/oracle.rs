@@ -0,0 +1,137 @@
+//! Price feed adapter: reads Pyth price updates (or a local mock with the same
+//! layout) and converts amounts between currencies for LTV checks.
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::constants::{BPS_DENOMINATOR, MAX_CLOCK_SKEW};
+use crate::error::ErrorCode;
+use crate::state::{CurrencyFeed, MockPriceFeed};
+
+/// Pyth Solana receiver; owns `PriceUpdateV2` accounts.
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Anchor discriminator of the receiver's `PriceUpdateV2` account.
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// ISO 4217 code of the unit every feed is quoted in (X/USD); needs no feed itself.
+pub const USD: [u8; 3] = *b"USD";
+
+/// How many Wormhole guardian signatures backed a Pyth update; only `Full` is trusted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, InitSpace)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+/// Pyth's price message, field for field.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, InitSpace)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Body of a `PriceUpdateV2` (and of `MockPriceFeed`), after the discriminator.
+#[derive(AnchorDeserialize)]
+struct PriceUpdate {
+    _write_authority: Pubkey,
+    verification_level: VerificationLevel,
+    price_message: PriceFeedMessage,
+    _posted_slot: u64,
+}
+
+/// A price in USD: `price * 10^exponent`, with the feed's confidence interval.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FeedPrice {
+    pub price: u64,
+    pub conf: u64,
+    pub exponent: i32,
+}
+
+impl FeedPrice {
+    /// One USD, exactly.
+    pub const ONE_USD: FeedPrice = FeedPrice { price: 1, conf: 0, exponent: 0 };
+
+    /// Low end of the confidence band: what collateral is valued at.
+    pub fn lower(&self) -> FeedPrice {
+        FeedPrice { price: self.price.saturating_sub(self.conf), conf: 0, exponent: self.exponent }
+    }
+
+    /// High end of the confidence band: what the loan currency is valued at.
+    pub fn upper(&self) -> Result<FeedPrice> {
+        let price = self.price.checked_add(self.conf).ok_or(ErrorCode::MathOverflow)?;
+        Ok(FeedPrice { price, conf: 0, exponent: self.exponent })
+    }
+}
+
+/// Reads and checks the price in `feed` for the currency registered in `config`.
+pub fn load_price(feed: &AccountInfo, config: &CurrencyFeed, now: i64) -> Result<FeedPrice> {
+    require_keys_eq!(feed.key(), config.feed, ErrorCode::InvalidPriceFeed);
+    let message = read_price_message(feed)?;
+    require!(message.feed_id == config.feed_id, ErrorCode::InvalidPriceFeed);
+
+    // Staleness: published recently, and not (meaningfully) in the future
+    let age = now.checked_sub(message.publish_time).ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        age <= config.max_staleness && age >= -MAX_CLOCK_SKEW,
+        ErrorCode::StalePrice
+    );
+
+    // Confidence: the band must be narrow relative to the price
+    require!(message.price > 0, ErrorCode::InvalidPriceFeed);
+    let price = message.price as u64;
+    let band = (message.conf as u128)
+        .checked_mul(BPS_DENOMINATOR as u128).ok_or(ErrorCode::MathOverflow)?;
+    let limit = (price as u128)
+        .checked_mul(config.max_confidence_bps as u128).ok_or(ErrorCode::MathOverflow)?;
+    require!(band <= limit, ErrorCode::PriceConfidenceTooWide);
+
+    Ok(FeedPrice { price, conf: message.conf, exponent: message.exponent })
+}
+
+/// Decodes a Pyth `PriceUpdateV2` or a `MockPriceFeed`; no freshness checks.
+pub fn read_price_message(feed: &AccountInfo) -> Result<PriceFeedMessage> {
+    let expected: &[u8] = if *feed.owner == PYTH_RECEIVER_ID {
+        &PRICE_UPDATE_V2_DISCRIMINATOR
+    } else if *feed.owner == crate::ID {
+        MockPriceFeed::DISCRIMINATOR
+    } else {
+        return err!(ErrorCode::InvalidPriceFeed);
+    };
+
+    let data = feed.try_borrow_data()?;
+    require!(data.len() >= 8 && data[..8] == *expected, ErrorCode::InvalidPriceFeed);
+    let update = PriceUpdate::deserialize(&mut &data[8..])
+        .map_err(|_| error!(ErrorCode::InvalidPriceFeed))?;
+    require!(update.verification_level == VerificationLevel::Full, ErrorCode::InvalidPriceFeed);
+    Ok(update.price_message)
+}
+
+/// Converts `amount` priced at `from` into units priced at `to` (both in USD), rounding down.
+pub fn convert(amount: u64, from: &FeedPrice, to: &FeedPrice) -> Result<u64> {
+    require!(to.price > 0, ErrorCode::InvalidPriceFeed);
+
+    // amount * from.price * 10^from.exponent / (to.price * 10^to.exponent)
+    let shift = from.exponent.checked_sub(to.exponent).ok_or(ErrorCode::MathOverflow)?;
+    let scale = 10u128.checked_pow(shift.unsigned_abs()).ok_or(ErrorCode::MathOverflow)?;
+    let mut numerator = (amount as u128)
+        .checked_mul(from.price as u128).ok_or(ErrorCode::MathOverflow)?;
+    let mut denominator = to.price as u128;
+    if shift >= 0 {
+        numerator = numerator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        denominator = denominator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    u64::try_from(numerator / denominator).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+/// Checks an ISO 4217-style code: three ASCII capital letters.
+pub fn validate_currency_code(code: &[u8]) -> Result<[u8; 3]> {
+    let code: [u8; 3] = code.try_into().map_err(|_| error!(ErrorCode::InvalidCurrencyCode))?;
+    require!(code.iter().all(u8::is_ascii_uppercase), ErrorCode::InvalidCurrencyCode);
+    Ok(code)
+}
@@ -0,0 +1,268 @@
+//! Oracle-signed booking proofs: the wire format and canonical signed bytes.
+//!
+//! Shared by the program and off-chain signers (depend on this crate with the
+//! `no-entrypoint` feature and sign `BookingProofEnvelope::signed_message()`),
+//! so both sides always agree on the bytes.
+use anchor_lang::prelude::*;
+use spl_token_2022::solana_program::hash::{hash, hashv};
+use crate::constants::MAX_LISTING_ID_LEN;
+use crate::error::ErrorCode;
+use crate::oracle::validate_currency_code;
+
+/// v1 signed bytes: tag || borsh(BookingProof). No extension area.
+pub const BOOKING_PROOF_DOMAIN_V1: &[u8] = b"rent_flow:booking:v1";
+/// v2+ signed bytes: tag || version || borsh(BookingProof) || borsh(extensions).
+pub const BOOKING_PROOF_DOMAIN: &[u8] = b"rent_flow:booking";
+
+pub const PROOF_VERSION_V1: u8 = 1;
+/// Signed bytes of one oracle signature over several proofs:
+/// tag || sha256(sha256(message_1) || ... || sha256(message_n)).
+pub const BOOKING_BATCH_DOMAIN: &[u8] = b"rent_flow:booking_batch";
+
+/// Newest schema this program understands; older ones stay accepted.
+pub const PROOF_VERSION_LATEST: u8 = 2;
+
+/// Upper bound on the TLV extension area.
+pub const MAX_PROOF_EXTENSIONS_LEN: usize = 512;
+/// Extension types with this bit set must be understood, or the proof is rejected.
+/// Unknown types without it are ignored, so oracles can add fields ahead of the program.
+pub const CRITICAL_EXTENSION: u8 = 0x80;
+/// Booking currency (3-byte ISO 4217 code). `amount` keeps the loan mint's scale
+/// but is priced in this currency; absent means priced in the loan currency.
+pub const EXT_BOOKING_CURRENCY: u8 = CRITICAL_EXTENSION | 0x01;
+/// Property id: the integrator's listing id (UTF-8, up to 32 bytes) of the Property
+/// the booking must be linked to.
+pub const EXT_PROPERTY_ID: u8 = CRITICAL_EXTENSION | 0x02;
+/// Extension types this program interprets.
+pub const KNOWN_EXTENSIONS: &[u8] = &[EXT_BOOKING_CURRENCY, EXT_PROPERTY_ID];
+
+/// TLV entry header: type (u8) + value length (u16 LE).
+const EXTENSION_HEADER_LEN: usize = 3;
+
+/// The fixed core every schema version carries.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BookingProof {
+    pub booking_id: String,
+    pub amount: u64,
+    pub start_date: i64,
+    pub end_date: i64,
+    pub host_wallet: Pubkey,
+    pub oracle_pubkey: Pubkey,
+    pub tier_index: u8,
+    pub investor_wallet: Pubkey,
+    pub issued_at: i64,   // When the oracle signed
+    pub expires_at: i64,  // Signature unusable after this
+    pub nonce: u64,       // Single-use per oracle, see NonceBitmap
+}
+
+/// A versioned proof: the core plus a TLV extension area (type u8 | len u16 LE | value).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BookingProofEnvelope {
+    pub version: u8,
+    pub proof: BookingProof,
+    pub extensions: Vec<u8>, // Always empty on v1
+}
+
+/// One parsed TLV entry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Extension<'a> {
+    pub kind: u8,
+    pub value: &'a [u8],
+}
+
+impl BookingProofEnvelope {
+    /// Canonical bytes the oracle signs for this envelope's version.
+    pub fn signed_message(&self) -> Result<Vec<u8>> {
+        require!(
+            (PROOF_VERSION_V1..=PROOF_VERSION_LATEST).contains(&self.version),
+            ErrorCode::UnsupportedProofVersion
+        );
+
+        if self.version == PROOF_VERSION_V1 {
+            require!(self.extensions.is_empty(), ErrorCode::MalformedProofExtensions);
+            let mut message = BOOKING_PROOF_DOMAIN_V1.to_vec();
+            self.proof.serialize(&mut message)?;
+            return Ok(message);
+        }
+
+        let mut message = BOOKING_PROOF_DOMAIN.to_vec();
+        message.push(self.version);
+        self.proof.serialize(&mut message)?;
+        self.extensions.serialize(&mut message)?;
+        Ok(message)
+    }
+
+    /// Parsed extensions; fails on malformed TLV or an unknown critical type.
+    pub fn extensions(&self) -> Result<Vec<Extension<'_>>> {
+        let extensions = parse_extensions(&self.extensions)?;
+        for extension in &extensions {
+            require!(
+                extension.kind & CRITICAL_EXTENSION == 0 || KNOWN_EXTENSIONS.contains(&extension.kind),
+                ErrorCode::MalformedProofExtensions
+            );
+        }
+        Ok(extensions)
+    }
+
+    /// Value of the extension of type `kind`, if present.
+    pub fn extension(&self, kind: u8) -> Result<Option<&[u8]>> {
+        Ok(self.extensions()?
+            .into_iter()
+            .find(|extension| extension.kind == kind)
+            .map(|extension| extension.value))
+    }
+
+    /// The booking currency extension, checked; None when the booking is priced in the loan currency.
+    pub fn booking_currency(&self) -> Result<Option<[u8; 3]>> {
+        self.extension(EXT_BOOKING_CURRENCY)?
+            .map(validate_currency_code)
+            .transpose()
+    }
+
+    /// The property id extension, checked; None when the oracle did not attest a property.
+    pub fn property_listing_id(&self) -> Result<Option<&[u8]>> {
+        let listing_id = self.extension(EXT_PROPERTY_ID)?;
+        if let Some(listing_id) = listing_id {
+            require!(
+                !listing_id.is_empty() && listing_id.len() <= MAX_LISTING_ID_LEN,
+                ErrorCode::MalformedProofExtensions
+            );
+        }
+        Ok(listing_id)
+    }
+}
+
+/// Bytes the oracle signs to attest a whole batch at once; order matters.
+pub fn batch_signed_message(messages: &[Vec<u8>]) -> Vec<u8> {
+    let digests: Vec<[u8; 32]> = messages.iter().map(|message| hash(message).to_bytes()).collect();
+    let parts: Vec<&[u8]> = digests.iter().map(|digest| digest.as_slice()).collect();
+
+    let mut signed = BOOKING_BATCH_DOMAIN.to_vec();
+    signed.extend_from_slice(&hashv(&parts).to_bytes());
+    signed
+}
+
+/// Splits a TLV area into entries; each type may appear at most once.
+pub fn parse_extensions(data: &[u8]) -> Result<Vec<Extension<'_>>> {
+    require!(data.len() <= MAX_PROOF_EXTENSIONS_LEN, ErrorCode::MalformedProofExtensions);
+
+    let mut extensions: Vec<Extension> = Vec::new();
+    let mut at = 0;
+    while at < data.len() {
+        let header = data
+            .get(at..at + EXTENSION_HEADER_LEN)
+            .ok_or(ErrorCode::MalformedProofExtensions)?;
+        let kind = header[0];
+        let len = u16::from_le_bytes([header[1], header[2]]) as usize;
+        at += EXTENSION_HEADER_LEN;
+
+        let value = data.get(at..at + len).ok_or(ErrorCode::MalformedProofExtensions)?;
+        at += len;
+
+        require!(
+            extensions.iter().all(|extension| extension.kind != kind),
+            ErrorCode::MalformedProofExtensions
+        );
+        extensions.push(Extension { kind, value });
+    }
+    Ok(extensions)
+}
+
+/// Builds a TLV area from (type, value) pairs, for signers.
+pub fn encode_extensions(entries: &[(u8, &[u8])]) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    for (kind, value) in entries {
+        let len = u16::try_from(value.len()).map_err(|_| error!(ErrorCode::MalformedProofExtensions))?;
+        data.push(*kind);
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(value);
+    }
+    parse_extensions(&data)?; // Same limits the program enforces
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn envelope(version: u8, extensions: Vec<u8>) -> BookingProofEnvelope {
+        BookingProofEnvelope {
+            version,
+            proof: BookingProof {
+                booking_id: "BK-1".to_string(),
+                amount: 1_000,
+                start_date: 10,
+                end_date: 20,
+                host_wallet: Pubkey::new_unique(),
+                oracle_pubkey: Pubkey::new_unique(),
+                tier_index: 0,
+                investor_wallet: Pubkey::new_unique(),
+                issued_at: 5,
+                expires_at: 50,
+                nonce: 7,
+            },
+            extensions,
+        }
+    }
+
+    #[test]
+    fn extensions_round_trip() {
+        let data = encode_extensions(&[(EXT_BOOKING_CURRENCY, b"EUR"), (0x05, b"")]).unwrap();
+        assert_eq!(
+            parse_extensions(&data).unwrap(),
+            vec![
+                Extension { kind: EXT_BOOKING_CURRENCY, value: b"EUR" },
+                Extension { kind: 0x05, value: b"" },
+            ]
+        );
+        assert!(parse_extensions(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn malformed_tlv_is_rejected() {
+        let malformed = Err(ErrorCode::MalformedProofExtensions.into());
+        // Header cut short, value cut short, a repeated type, an oversized area
+        assert_eq!(parse_extensions(&[EXT_BOOKING_CURRENCY, 3]), malformed);
+        assert_eq!(parse_extensions(&[EXT_BOOKING_CURRENCY, 3, 0, b'E', b'U']), malformed);
+        let twice = [[0x05, 1, 0, 1], [0x05, 1, 0, 2]].concat();
+        assert_eq!(parse_extensions(&twice), malformed);
+        assert_eq!(parse_extensions(&[0u8; MAX_PROOF_EXTENSIONS_LEN + 1]), malformed);
+    }
+
+    #[test]
+    fn unknown_extensions_are_rejected_only_when_critical() {
+        let optional = encode_extensions(&[(0x7f, b"later")]).unwrap();
+        assert!(envelope(2, optional).extensions().is_ok());
+
+        let critical = encode_extensions(&[(CRITICAL_EXTENSION | 0x7f, b"later")]).unwrap();
+        assert_eq!(envelope(2, critical).extensions(), Err(ErrorCode::MalformedProofExtensions.into()));
+    }
+
+    #[test]
+    fn known_extensions_are_checked() {
+        let currency = encode_extensions(&[(EXT_BOOKING_CURRENCY, b"EUR")]).unwrap();
+        assert_eq!(envelope(2, currency).booking_currency().unwrap(), Some(*b"EUR"));
+
+        let empty_listing = encode_extensions(&[(EXT_PROPERTY_ID, b"")]).unwrap();
+        assert!(envelope(2, empty_listing).property_listing_id().is_err());
+        let long_listing = [b'x'; MAX_LISTING_ID_LEN + 1];
+        let long_listing = encode_extensions(&[(EXT_PROPERTY_ID, &long_listing)]).unwrap();
+        assert!(envelope(2, long_listing).property_listing_id().is_err());
+    }
+
+    #[test]
+    fn signed_bytes_are_bound_to_the_version() {
+        let v1 = envelope(1, Vec::new());
+        let mut v2 = v1.clone();
+        v2.version = 2;
+        assert!(v1.signed_message().unwrap().starts_with(BOOKING_PROOF_DOMAIN_V1));
+        assert_ne!(v1.signed_message().unwrap(), v2.signed_message().unwrap());
+
+        // v1 has no extension area, and unknown versions are refused outright
+        assert!(envelope(1, vec![0x05, 0, 0]).signed_message().is_err());
+        assert_eq!(
+            envelope(PROOF_VERSION_LATEST + 1, Vec::new()).signed_message(),
+            Err(ErrorCode::UnsupportedProofVersion.into())
+        );
+    }
+}
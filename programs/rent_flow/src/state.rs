@@ -1,6 +1,10 @@
 // Step 1: Import the necessary Anchor framework components
 // Anchor provides prelude that includes commonly used types and traits
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::math::bps_of;
+use crate::oracle::{PriceFeedMessage, VerificationLevel, USD};
 
 // Step 2: Define the account structure using the #[account] attribute macro
 // This macro tells Anchor that this struct represents an on-chain account
@@ -10,6 +14,24 @@ pub struct IntegratorConfig {
     pub bump: u8,
     pub is_active: bool,
     pub authority: Pubkey, // The wallet that can manage this config
+    pub outstanding_principal: u64, // Principal currently lent against bookings this integrator vouched for
+    pub grace_period: i64, // Grace override for this integrator's bookings; 0 = inherit
+    pub version: u8,       // Layout version, see INTEGRATOR_VERSION
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct IntegratorBond {
+    pub integrator_config: Pubkey, // The integrator this bond backs
+    pub mint: Pubkey,              // Bond currency (USDC)
+    pub amount: u64,               // Active, slashable bond
+    pub pending_unbond: u64,       // Requested for withdrawal, still slashable until released
+    pub unbond_available_at: i64,  // When the pending amount can be withdrawn
+    pub total_slashed: u64,        // Lifetime amount slashed into the pool
+    pub bump: u8,
+    pub version: u8,               // Layout version, see INTEGRATOR_BOND_VERSION
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES],
 }
 
 #[account]
@@ -33,11 +55,54 @@ pub struct BookingObligation {
     pub is_locked: bool,   // True if currently used as collateral
     pub is_settled: bool,  // True if booking is completed/paid out
     pub tier: ProfitTier,  // The profit tier for this booking
+    pub status: ObligationStatus, // Lifecycle of the loan (Active -> Repaid | Defaulted -> Recovered)
+
+    pub defaulted_at: i64,       // Seizure timestamp
+    pub recovered_amount: u64,   // Auction proceeds paid into the pool
+    pub reserve_covered: u64,    // First-loss cover drawn from the reserve fund
+    pub bond_slashed: u64,       // Integrator bond slashed into the pool on default
+    pub rate_bps: u64,           // Borrow rate locked in when the loan was funded
     
     // Padding for alignment (optional but good practice)
     pub bump: u8,
+    pub version: u8,             // Layout version, see OBLIGATION_VERSION
+
+    // New fields are carved out of _reserved; a version that does not fit in what is left
+    // grows the account by another ACCOUNT_RESERVED_BYTES block (migrate_obligation reallocs)
+
+    // --- v2 --- (reserve: 64 -> 32)
+    pub payout_reference: [u8; 32], // Oracle-attested platform payout that repaid the loan (settle_on_behalf)
+
+    // --- v3 --- (outgrew the reserve: +1 block, 96 -> 48)
+    pub liquidator: Pubkey,  // Who seized the collateral; owed any unpaid bounty
+    pub bounty_paid: u64,    // Bounty the pool advanced at seizure (recovered from the auction)
+    pub bounty_owed: u64,    // Bounty deferred to the auction proceeds
+
+    // --- v4 --- (48 -> 40)
+    pub grace_period: i64,   // Effective grace period, snapshotted when the loan was funded
+
+    // --- v5 --- (40 -> 32)
+    pub redeem_deadline: i64, // Host can redeem seized collateral until then; auctions start after
+
+    // --- v6 --- (32 -> 0)
+    pub loan_mint: Pubkey,    // Currency the loan was disbursed in (SupportedToken mint)
+
+    // --- v7 --- (outgrew the reserve: +1 block, 64 -> 61)
+    pub booking_currency: [u8; 3], // ISO code booking_value is priced in; zeroed = the loan currency
+
+    // --- v8 --- (61 -> 29)
+    pub property: Pubkey,          // Property PDA the booking is for; default = not linked
+
+    // --- v9 --- (29 -> 12)
+    pub default_cured: bool,       // The admin cleared this default from the host's profile (cure_host_default)
+    pub reserve_slashed: u64,      // Integrator bond (other currency) slashed into the reserve on default
+    pub integrator_escrowed: u64,  // Part of the repayment escrow the integrator funded; the host funded the rest
+    _reserved: [u8; OBLIGATION_RESERVED_BYTES],
 }
 
+/// What is left of BookingObligation's reserve: v2-v9 carved 180 bytes out of three blocks.
+const OBLIGATION_RESERVED_BYTES: usize = 3 * ACCOUNT_RESERVED_BYTES - 180;
+
 // Step 4: Implement methods for the BookingObligation struct
 impl BookingObligation {
     // Step 5: Define a constant for the account size
@@ -60,6 +125,103 @@ impl BookingObligation {
     //     8 +  // borrowed_amount
     //     1 +  // locked_status
     //     8;   // expiry   
+
+    /// A loan was disbursed against the booking and is not yet repaid or seized.
+    pub fn has_active_loan(&self) -> bool {
+        self.status == ObligationStatus::Active && self.borrowed_amount > 0
+    }
+
+    /// The integrator's share of `remaining`, what is left of an escrow that held `escrowed`:
+    /// the host and the integrator get back pro rata to what each funded.
+    pub fn integrator_escrow_share(&self, remaining: u64, escrowed: u64) -> Result<u64> {
+        if escrowed == 0 {
+            return Ok(0);
+        }
+        let share = (remaining as u128)
+            .checked_mul(self.integrator_escrowed as u128).ok_or(ErrorCode::MathOverflow)?
+            / escrowed as u128;
+        Ok(share.min(remaining as u128) as u64)
+    }
+
+    /// The obligation was seized and its default still counts against the host's `active_defaults`.
+    pub fn has_open_default(&self) -> bool {
+        matches!(
+            self.status,
+            ObligationStatus::Defaulted | ObligationStatus::Recovered | ObligationStatus::WrittenOff
+        ) && !self.default_cured
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct HostProfile {
+    pub host: Pubkey,
+
+    // --- Repayment History ---
+    pub on_time_count: u32,  // Settled within the on-time window after end_date
+    pub early_count: u32,    // Settled before end_date (early exit)
+    pub late_count: u32,     // Settled after the on-time window
+    pub default_count: u32,  // Lifetime liquidations
+    pub active_defaults: u32, // Defaults not yet cured; blocks new borrowing
+
+    // --- Volume ---
+    pub total_borrowed: u64,
+    pub total_repaid: u64,
+    pub outstanding_principal: u64, // Live exposure: principal currently lent to this host
+
+    pub bump: u8,
+    pub version: u8, // Layout version, see HOST_PROFILE_VERSION
+    pub registered_properties: u32, // Listings registered to this host; their bookings must be linked to borrow
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES - 4],
+}
+
+impl HostProfile {
+    pub fn is_blocked(&self) -> bool {
+        self.active_defaults > 0
+    }
+
+    /// Books a repayment of `principal` (`total` incl. charges) landing at `now` against `end_date`.
+    pub fn record_repayment(&mut self, end_date: i64, now: i64, principal: u64, total: u64) -> Result<()> {
+        let on_time_deadline = end_date
+            .checked_add(ON_TIME_WINDOW)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if now < end_date {
+            self.early_count = self.early_count.saturating_add(1);
+        } else if now <= on_time_deadline {
+            self.on_time_count = self.on_time_count.saturating_add(1);
+        } else {
+            self.late_count = self.late_count.saturating_add(1);
+        }
+        self.total_repaid = self.total_repaid
+            .checked_add(total)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.outstanding_principal = self.outstanding_principal
+            .checked_sub(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    fn good_repayments(&self) -> u64 {
+        self.on_time_count as u64 + self.early_count as u64
+    }
+
+    /// LTV after reputation: clean repayments earn headroom, lateness and defaults cost it.
+    pub fn adjusted_ltv_bps(&self, base_ltv_bps: u64) -> u64 {
+        let bonus = (self.good_repayments() * REP_LTV_BONUS_BPS).min(REP_MAX_LTV_BONUS_BPS);
+        let malus = self.late_count as u64 * REP_LATE_LTV_PENALTY_BPS
+            + self.default_count as u64 * REP_DEFAULT_LTV_PENALTY_BPS;
+
+        (base_ltv_bps + bonus).saturating_sub(malus).min(BPS_DENOMINATOR)
+    }
+
+    /// Borrow rate after reputation: clean repayments earn a discount, lateness a surcharge.
+    pub fn adjusted_rate_bps(&self, base_rate_bps: u64) -> u64 {
+        let discount = (self.good_repayments() * REP_RATE_DISCOUNT_BPS).min(REP_MAX_RATE_DISCOUNT_BPS);
+        let surcharge = self.late_count as u64 * REP_LATE_RATE_SURCHARGE_BPS
+            + self.default_count as u64 * REP_DEFAULT_RATE_SURCHARGE_BPS;
+
+        base_rate_bps.saturating_sub(discount) + surcharge
+    }
 }
 
 #[account]
@@ -87,8 +249,23 @@ pub struct SupportedToken {
     // Critical for PDA (Program Derived Address) validation
     // Stores the bump seed used to create this PDA
     // Used to verify the PDA was created correctly
-    _reserved: [u8; 7],
-    
+
+    // Layout version - 1 byte, carved from the reserved area
+    // Accounts listed before versioning read 0 here
+    pub version: u8,
+
+    // --- v2: this currency's lending pool; the cash sits in the token's vault ATA ---
+    pub total_liquidity_tracked: u64, // Cash in the vault + loans outstanding
+    pub outstanding_principal: u64,   // Currently lent out to hosts
+    pub total_deposits: u64,          // LP principal; the base yield is shared over
+    pub total_yield_earned: u64,      // Lifetime interest + penalties credited to LPs
+    pub acc_yield_per_share: u128,    // Yield per share, scaled by YIELD_PRECISION
+    pub loss_index: u128,             // Value of one unit of original principal, scaled by YIELD_PRECISION
+    pub total_losses: u64,            // Lifetime bad debt socialized across LPs
+
+    // --- v3 ---
+    pub currency: [u8; 3],            // ISO code the token tracks (e.g. "USD" for USDC), for price feeds
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES - 3],
 }
 
 // Line 10: Implementation block for SupportedToken struct
@@ -105,6 +282,71 @@ impl SupportedToken {
     //           │     │    └─ 8 bytes for Anchor discriminator
     //           │     └─ Total size in bytes
     //           └─ Compile-time constant
+
+    /// Credits settlement yield to LPs pro-rata to principal; it stays in the pool as liquidity.
+    pub fn distribute_yield(&mut self, amount: u64) -> Result<()> {
+        if self.total_deposits > 0 {
+            // Shares are principal in pre-loss units, so yield keeps accruing pro-rata after a write-down
+            let total_shares = (self.total_deposits as u128)
+                .checked_mul(YIELD_PRECISION).ok_or(ErrorCode::MathOverflow)?
+                / self.loss_index;
+            let per_share = (amount as u128)
+                .checked_mul(YIELD_PRECISION).ok_or(ErrorCode::MathOverflow)?
+                / total_shares.max(1);
+            self.acc_yield_per_share = self.acc_yield_per_share
+                .checked_add(per_share)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        self.total_yield_earned = self.total_yield_earned
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_liquidity_tracked = self.total_liquidity_tracked
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Writes LP principal down pro-rata by a realized loss; positions pick it up on harvest.
+    pub fn realize_loss(&mut self, loss: u64) -> Result<()> {
+        let loss = loss.min(self.total_deposits);
+        if loss == 0 {
+            return Ok(());
+        }
+        let remaining = self.total_deposits - loss;
+        self.loss_index = (self.loss_index
+            .checked_mul(remaining as u128).ok_or(ErrorCode::MathOverflow)?
+            / self.total_deposits as u128)
+            .max(1); // A fully wiped pool keeps a non-zero index so new deposits still price
+        self.total_deposits = remaining;
+        self.total_liquidity_tracked = self.total_liquidity_tracked.saturating_sub(loss);
+        self.total_losses = self.total_losses
+            .checked_add(loss)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Liquidity not currently lent out, i.e. what can fund a new loan or an LP exit.
+    pub fn available_liquidity(&self) -> u64 {
+        self.total_liquidity_tracked.saturating_sub(self.outstanding_principal)
+    }
+
+    /// Share of pool liquidity lent out once `new_borrow` is disbursed, capped at 100%.
+    pub fn utilization_bps(&self, new_borrow: u64) -> Result<u64> {
+        let borrowed = self.outstanding_principal
+            .checked_add(new_borrow)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if borrowed == 0 {
+            return Ok(0);
+        }
+        if self.total_liquidity_tracked == 0 {
+            return Ok(BPS_DENOMINATOR);
+        }
+
+        let utilization = (borrowed as u128)
+            .checked_mul(BPS_DENOMINATOR as u128).ok_or(ErrorCode::MathOverflow)?
+            / self.total_liquidity_tracked as u128;
+        Ok((utilization as u64).min(BPS_DENOMINATOR))
+    }
 }
 
 #[account]
@@ -115,19 +357,951 @@ pub struct Position {
     pub realized_profit: u64,
     pub unlock_ts: i64,
     pub bump: u8,
+    pub version: u8, // Layout version, see POSITION_VERSION
+
+    // --- v2 ---
+    pub yield_debt: u128, // shares * acc_yield_per_share at the last checkpoint
+
+    // --- v3 ---
+    pub loss_index: u128, // SupportedToken.loss_index when `principal` was last re-based
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES - 32],
+}
+
+impl Position {
+    /// Principal expressed in pre-loss units; unchanged by write-downs.
+    fn shares(&self) -> Result<u128> {
+        if self.principal == 0 {
+            return Ok(0);
+        }
+        Ok((self.principal as u128)
+            .checked_mul(YIELD_PRECISION).ok_or(ErrorCode::MathOverflow)?
+            / self.loss_index)
+    }
+
+    fn entitled_yield(&self, acc_yield_per_share: u128) -> Result<u128> {
+        Ok(self.shares()?
+            .checked_mul(acc_yield_per_share).ok_or(ErrorCode::MathOverflow)?
+            / YIELD_PRECISION)
+    }
+
+    /// Moves yield earned since the last checkpoint into `realized_profit` and applies
+    /// any loss written off since then to `principal`.
+    pub fn harvest(&mut self, pool: &SupportedToken) -> Result<()> {
+        let pending = self.entitled_yield(pool.acc_yield_per_share)?.saturating_sub(self.yield_debt);
+        let pending = u64::try_from(pending).map_err(|_| error!(ErrorCode::MathOverflow))?;
+        self.realized_profit = self.realized_profit
+            .checked_add(pending)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let written_down = self.shares()?
+            .checked_mul(pool.loss_index).ok_or(ErrorCode::MathOverflow)?
+            / YIELD_PRECISION;
+        self.principal = u64::try_from(written_down).map_err(|_| error!(ErrorCode::MathOverflow))?;
+        self.checkpoint(pool)
+    }
+
+    /// Re-bases the yield debt and loss index after `principal` changes.
+    pub fn checkpoint(&mut self, pool: &SupportedToken) -> Result<()> {
+        self.loss_index = pool.loss_index;
+        self.yield_debt = self.entitled_yield(pool.acc_yield_per_share)?;
+        Ok(())
+    }
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct PoolVault {
+    // Books of the single-currency pool (v1-v4). Lending books live on each SupportedToken
+    // now; migrate_pool_liquidity moves these there and zeroes them.
     pub total_liquidity_tracked: u64,
+    pub outstanding_principal: u64, // USDC currently lent out to hosts
+    pub rate_model: RateModel,      // Kinked utilization curve pricing new loans
+    pub bump: u8,
+    pub version: u8,                // Layout version, see POOL_VAULT_VERSION
+
+    // --- v2 ---
+    pub total_deposits: u64,        // LP principal; the base yield is shared over
+    pub total_yield_earned: u64,    // Lifetime interest + penalties credited to LPs
+    pub acc_yield_per_share: u128,  // Yield per unit of principal, scaled by YIELD_PRECISION
+
+    // --- v3 ---
+    pub grace_period: i64,             // Pool-wide default grace period
+    pub tier_grace_periods: [i64; 4],  // Per-ProfitTier overrides; 0 = inherit the pool default
+
+    // --- v4 ---
+    pub loss_index: u128,              // Value of one unit of original principal, scaled by YIELD_PRECISION
+    pub total_losses: u64,             // Lifetime bad debt socialized across LPs
+
+    // --- v5 ---
+    pub base_mint: Pubkey,             // Currency of the legacy books, set by migrate_pool_liquidity
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES - 56],
+}
+
+impl PoolVault {
+    /// Effective grace period. Precedence: integrator override > tier override > pool default.
+    pub fn grace_period_for(&self, tier: ProfitTier, integrator_override: i64) -> i64 {
+        if integrator_override > 0 {
+            return integrator_override;
+        }
+        match self.tier_grace_periods[tier as usize] {
+            0 => self.grace_period,
+            tier_override => tier_override,
+        }
+    }
+}
+
+/// Two-slope interest curve: rates rise gently up to the kink, then steeply.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default, PartialEq, Debug)]
+pub struct RateModel {
+    pub base_rate_bps: u16, // Rate at 0% utilization
+    pub slope1_bps: u16,    // Added across 0% -> kink
+    pub kink_bps: u16,      // Optimal utilization
+    pub slope2_bps: u16,    // Added across kink -> 100%
+}
+
+impl RateModel {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.kink_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+        Ok(())
+    }
+
+    pub fn borrow_rate_bps(&self, utilization_bps: u64) -> Result<u64> {
+        let base = self.base_rate_bps as u64;
+        let kink = self.kink_bps as u64;
+        let utilization = utilization_bps.min(BPS_DENOMINATOR);
+
+        if utilization <= kink {
+            if kink == 0 {
+                return Ok(base);
+            }
+            let climb = (self.slope1_bps as u64)
+                .checked_mul(utilization).ok_or(ErrorCode::MathOverflow)?
+                / kink;
+            return Ok(base + climb);
+        }
+
+        let steep = (self.slope2_bps as u64)
+            .checked_mul(utilization - kink).ok_or(ErrorCode::MathOverflow)?
+            / (BPS_DENOMINATOR - kink);
+        Ok(base + self.slope1_bps as u64 + steep)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,     // The wallet allowed to tune protocol parameters
+    pub guardian: Pubkey,  // Emergency key: can pause, never unpause
+    pub oracle: Pubkey,    // Key whose Ed25519 attestations the program trusts
+    pub paused: u8,        // Bitmask of PAUSE_* flags
+    pub reserve_bps: u16,  // Slice of settlement interest + penalties routed to the reserve
+    pub protocol_fee_bps: u16, // Slice of settlement interest + penalties routed to the treasury
+    pub total_fees_collected: u64, // Lifetime treasury inflows
+
+    // --- Integrator Bond Parameters ---
+    pub min_bond_amount: u64,   // Absolute bond floor required to be activated
+    pub min_bond_bps: u16,      // Bond required relative to outstanding originated principal
+    pub slash_bps: u16,         // Share of defaulted debt slashed from the bond
+    pub unbonding_period: i64,  // Delay between an unbond request and the withdrawal
+
+    pub exposure_limits: ExposureLimits, // Concentration caps enforced on every borrow
+    pub liquidation_bounty: LiquidationBounty, // Reward for seizing a defaulted booking
+    pub redemption: RedemptionTerms,           // Host's right to cure after seizure
+
+    // --- Timelock ---
+    pub timelock_delay: i64, // Minimum wait between queueing and executing a parameter change
+    pub change_count: u64,   // Next PendingChange id
+    pub bump: u8,
+    pub version: u8,         // Layout version, see PROTOCOL_CONFIG_VERSION
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES],
+}
+
+impl ProtocolConfig {
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    /// Bond an integrator must hold while `outstanding` principal is originated through it.
+    pub fn required_bond(&self, outstanding: u64) -> Result<u64> {
+        Ok(bps_of(outstanding, self.min_bond_bps as u64)?.max(self.min_bond_amount))
+    }
+}
+
+/// Concentration caps. A zero value disables that particular cap.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default, PartialEq, Debug)]
+pub struct ExposureLimits {
+    pub max_host_principal: u64,       // Absolute cap on one host's outstanding principal
+    pub max_host_bps: u16,             // ... as a share of pool assets
+    pub max_integrator_principal: u64, // Absolute cap on principal originated via one integrator
+    pub max_integrator_bps: u16,       // ... as a share of pool assets
+    pub max_booking_principal: u64,    // Absolute cap on a single booking loan
+    pub max_booking_bps: u16,          // ... as a share of pool assets
+}
+
+impl ExposureLimits {
+    pub fn validate(&self) -> Result<()> {
+        for bps in [self.max_host_bps, self.max_integrator_bps, self.max_booking_bps] {
+            require!(bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+        }
+        Ok(())
+    }
+
+    /// Tightest of the absolute and pool-relative caps, `None` when both are disabled.
+    fn cap(absolute: u64, bps: u16, pool_assets: u64) -> Result<Option<u64>> {
+        let relative = if bps > 0 { Some(bps_of(pool_assets, bps as u64)?) } else { None };
+        let absolute = if absolute > 0 { Some(absolute) } else { None };
+        Ok(match (absolute, relative) {
+            (Some(a), Some(r)) => Some(a.min(r)),
+            (a, r) => a.or(r),
+        })
+    }
+
+    /// Checks a new loan against every cap, using exposures that already include it.
+    pub fn check(&self, pool_assets: u64, booking: u64, host_after: u64, integrator_after: u64) -> Result<()> {
+        if let Some(cap) = Self::cap(self.max_booking_principal, self.max_booking_bps, pool_assets)? {
+            require!(booking <= cap, ErrorCode::BookingExposureExceeded);
+        }
+        if let Some(cap) = Self::cap(self.max_host_principal, self.max_host_bps, pool_assets)? {
+            require!(host_after <= cap, ErrorCode::HostExposureExceeded);
+        }
+        if let Some(cap) = Self::cap(self.max_integrator_principal, self.max_integrator_bps, pool_assets)? {
+            require!(integrator_after <= cap, ErrorCode::IntegratorExposureExceeded);
+        }
+        Ok(())
+    }
+}
+
+/// Liquidator reward: fixed + bps of the outstanding debt, capped.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default, PartialEq, Debug)]
+pub struct LiquidationBounty {
+    pub fixed: u64,
+    pub bps: u16,
+    pub cap: u64,
+}
+
+impl LiquidationBounty {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+        Ok(())
+    }
+
+    pub fn amount(&self, debt: u64) -> Result<u64> {
+        Ok(self.fixed
+            .checked_add(bps_of(debt, self.bps as u64)?)
+            .ok_or(ErrorCode::MathOverflow)?
+            .min(self.cap))
+    }
+}
+
+/// How long a host can buy back seized collateral, and what it costs on top of the debt.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default, PartialEq, Debug)]
+pub struct RedemptionTerms {
+    pub window: i64,       // Seconds after seizure; auctions and write-offs wait for it
+    pub penalty_bps: u16,  // Default penalty on principal
+}
+
+impl RedemptionTerms {
+    pub fn validate(&self) -> Result<()> {
+        require!((0..=MAX_REDEMPTION_WINDOW).contains(&self.window), ErrorCode::InvalidAmount);
+        require!(self.penalty_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingChange {
+    pub id: u64,
+    pub change: ParamChange,
+    pub eta: i64,          // Earliest execution time
+    pub proposer: Pubkey,  // Receives the rent back on execute / cancel
+    pub bump: u8,
+}
+
+/// A typed protocol parameter change that must sit in the timelock before it applies.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Debug)]
+pub enum ParamChange {
+    TokenLtv { mint: Pubkey, ltv_bps: u16 },
+    ReserveBps { reserve_bps: u16 },
+    ProtocolFeeBps { fee_bps: u16 },
+    BondParams { min_bond_amount: u64, min_bond_bps: u16, slash_bps: u16, unbonding_period: i64 },
+    ExposureLimits { limits: ExposureLimits },
+    GracePeriods { pool: i64, tiers: [i64; 4] },
+    IntegratorGracePeriod { integrator: Pubkey, grace_period: i64 },
+    RateModel { rate_model: RateModel },
+    TimelockDelay { delay: i64 },
+    Oracle { oracle: Pubkey },
+    LiquidationBounty { bounty: LiquidationBounty },
+    Redemption { terms: RedemptionTerms },
+}
+
+impl ParamChange {
+    /// Rejects bad values at queue time so LPs never watch an unexecutable change.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ParamChange::TokenLtv { ltv_bps, .. } => {
+                require!(*ltv_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+            }
+            ParamChange::ReserveBps { reserve_bps } => {
+                require!(*reserve_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+            }
+            ParamChange::ProtocolFeeBps { fee_bps } => {
+                require!(*fee_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+            }
+            ParamChange::BondParams { min_bond_bps, slash_bps, unbonding_period, .. } => {
+                require!(*min_bond_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+                require!(*slash_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidBps);
+                require!(*unbonding_period >= 0, ErrorCode::InvalidAmount);
+            }
+            ParamChange::ExposureLimits { limits } => limits.validate()?,
+            ParamChange::GracePeriods { pool, tiers } => {
+                for grace in std::iter::once(pool).chain(tiers.iter()) {
+                    require!((0..=MAX_GRACE_PERIOD).contains(grace), ErrorCode::InvalidGracePeriod);
+                }
+            }
+            ParamChange::IntegratorGracePeriod { grace_period, .. } => {
+                require!((0..=MAX_GRACE_PERIOD).contains(grace_period), ErrorCode::InvalidGracePeriod);
+            }
+            ParamChange::RateModel { rate_model } => rate_model.validate()?,
+            ParamChange::TimelockDelay { delay } => {
+                require!(*delay >= 0, ErrorCode::InvalidAmount);
+            }
+            ParamChange::Oracle { oracle } => {
+                require!(*oracle != Pubkey::default(), ErrorCode::InvalidOracleKey);
+            }
+            ParamChange::LiquidationBounty { bounty } => bounty.validate()?,
+            ParamChange::Redemption { terms } => terms.validate()?,
+        }
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AdminMultisig {
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub signers: Vec<Pubkey>,  // Ops team keys; index = approval bit
+    pub threshold: u8,         // Approvals (M) required out of signers (N)
+    pub proposal_count: u64,   // Next AdminProposal id
+    pub authority_bump: u8,    // Bump of the system-owned authority PDA that acts as protocol admin
+    pub bump: u8,
+}
+
+impl AdminMultisig {
+    pub fn signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.signers.iter().position(|signer| signer == key)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AdminProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    #[max_len(MAX_PROPOSAL_ACCOUNTS)]
+    pub accounts: Vec<ProposalAccount>, // Exact account list the action executes against
+    pub approvals: u16,                 // Bitmap over AdminMultisig.signers
+    pub executed: bool,
     pub bump: u8,
 }
 
+impl AdminProposal {
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Debug)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
+
+/// Admin instructions the multisig can execute. Each maps 1:1 to a program instruction,
+/// invoked with the multisig authority PDA signing as `admin`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Debug)]
+pub enum AdminAction {
+    SetIntegratorStatus { is_active: bool },
+    ListToken { ltv_bps: u16, currency: [u8; 3] },
+    Pause { mask: u8 },
+    Unpause { mask: u8 },
+    QueueChange { change: ParamChange },
+    SetGuardian { guardian: Pubkey },
+    TransferAdmin { new_admin: Pubkey },
+    SetCurrencyFeed { currency: [u8; 3], feed_id: [u8; 32], max_staleness: i64, max_confidence_bps: u16 },
+}
+
+impl AdminAction {
+    /// Serialized instruction data (discriminator + args) for the mapped instruction.
+    pub fn instruction_data(&self) -> Vec<u8> {
+        use crate::instruction;
+        use anchor_lang::InstructionData;
+
+        match *self {
+            AdminAction::SetIntegratorStatus { is_active } => instruction::SetIntegratorStatus { is_active }.data(),
+            AdminAction::ListToken { ltv_bps, currency } => instruction::AddToken { ltv_bps, currency }.data(),
+            AdminAction::Pause { mask } => instruction::Pause { mask }.data(),
+            AdminAction::Unpause { mask } => instruction::Unpause { mask }.data(),
+            AdminAction::QueueChange { change } => instruction::QueueChange { change }.data(),
+            AdminAction::SetGuardian { guardian } => instruction::SetGuardian { guardian }.data(),
+            AdminAction::TransferAdmin { new_admin } => instruction::TransferAdmin { new_admin }.data(),
+            AdminAction::SetCurrencyFeed { currency, feed_id, max_staleness, max_confidence_bps } => {
+                instruction::SetCurrencyFeed { currency, feed_id, max_staleness, max_confidence_bps }.data()
+            }
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReserveFund {
+    pub total_contributed: u64, // Lifetime inflows from settlements
+    pub total_covered: u64,     // Lifetime shortfall paid out to the pool
+    pub bump: u8,
+    pub version: u8,            // Layout version, see RESERVE_FUND_VERSION
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES],
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Copy, PartialEq, Debug)]
 pub enum ProfitTier {
     OneMonth,    // 2.5%
     ThreeMonth,  // 3.5%
     SixMonth,    // 4.5%
     TwelveMonth, // 6.5%
-}
\ No newline at end of file
+}
+
+impl ProfitTier {
+    const DAY: i64 = 60 * 60 * 24;
+
+    pub fn from_index(index: u8) -> Result<Self> {
+        match index {
+            0 => Ok(ProfitTier::OneMonth),
+            1 => Ok(ProfitTier::ThreeMonth),
+            2 => Ok(ProfitTier::SixMonth),
+            3 => Ok(ProfitTier::TwelveMonth),
+            _ => err!(ErrorCode::InvalidTier),
+        }
+    }
+
+    /// The tier a booking of `duration` seconds belongs to, if any.
+    pub fn for_duration(duration: i64) -> Option<Self> {
+        match duration {
+            d if d <= 0 => None,
+            d if d <= 31 * Self::DAY => Some(ProfitTier::OneMonth),
+            d if d <= 92 * Self::DAY => Some(ProfitTier::ThreeMonth),
+            d if d <= 183 * Self::DAY => Some(ProfitTier::SixMonth),
+            d if d <= 366 * Self::DAY => Some(ProfitTier::TwelveMonth),
+            _ => None,
+        }
+    }
+}
+
+/// Replay guard: one bit per oracle nonce, NONCES_PER_PAGE nonces per page.
+#[account]
+#[derive(InitSpace)]
+pub struct NonceBitmap {
+    pub oracle: Pubkey,
+    pub page: u64,
+    pub bits: [u8; NONCE_PAGE_BYTES],
+    pub bump: u8,
+}
+
+impl NonceBitmap {
+    /// Marks `nonce` as used; fails if it already was.
+    pub fn consume(&mut self, nonce: u64) -> Result<()> {
+        let bit = nonce % NONCES_PER_PAGE;
+        let (byte, mask) = ((bit / 8) as usize, 1u8 << (bit % 8));
+        require!(self.bits[byte] & mask == 0, ErrorCode::NonceAlreadyUsed);
+        self.bits[byte] |= mask;
+        Ok(())
+    }
+}
+
+/// A listing registered by its integrator; obligations link to it so risk can be
+/// read per property, not just per host.
+#[account]
+#[derive(InitSpace)]
+pub struct Property {
+    pub integrator_config: Pubkey, // Integrator that registered the listing
+    #[max_len(32)]
+    pub listing_id: String,        // The integrator's own listing id, part of the seeds
+    pub host: Pubkey,              // Host allowed to mint bookings for it
+    #[max_len(16)]
+    pub region: String,            // e.g. an ISO 3166-2 code ("PT-11")
+    pub registered_at: i64,
+
+    // --- Exposure ---
+    pub total_bookings: u32,        // Bookings minted against the property
+    pub active_bookings: u32,       // Funded loans not yet repaid or resolved
+    pub outstanding_principal: u64, // Principal currently lent against it
+    pub total_borrowed: u64,
+
+    // --- Default History ---
+    pub default_count: u32,    // Lifetime liquidations
+    pub open_defaults: u32,    // Seized and not yet redeemed, sold or written off
+    pub last_default_at: i64,
+    pub total_shortfall: u64,  // Debt left unrecovered by auctions and write-offs
+
+    pub bump: u8,
+    pub version: u8, // Layout version, see PROPERTY_VERSION
+    _reserved: [u8; ACCOUNT_RESERVED_BYTES],
+}
+
+impl Property {
+    /// The obligation's property, if it has one; a linked property must be passed
+    /// (the account constraint pins it to `obligation.property`).
+    pub fn linked<'a, 'info>(
+        obligation: &BookingObligation,
+        property: &'a mut Option<Account<'info, Property>>,
+    ) -> Result<Option<&'a mut Account<'info, Property>>> {
+        if obligation.property == Pubkey::default() {
+            return Ok(None);
+        }
+        property.as_mut().map(Some).ok_or(error!(ErrorCode::MissingProperty))
+    }
+
+    /// A loan of `principal` was funded against one of its bookings.
+    pub fn record_funding(&mut self, principal: u64) -> Result<()> {
+        self.active_bookings = self.active_bookings.saturating_add(1);
+        self.outstanding_principal = self.outstanding_principal
+            .checked_add(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_borrowed = self.total_borrowed
+            .checked_add(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// A loan of `principal` against it was repaid (settled or redeemed).
+    pub fn record_repayment(&mut self, principal: u64) -> Result<()> {
+        self.active_bookings = self.active_bookings.saturating_sub(1);
+        self.outstanding_principal = self.outstanding_principal
+            .checked_sub(principal)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// One of its loans was liquidated at `now`.
+    pub fn record_default(&mut self, now: i64) {
+        self.default_count = self.default_count.saturating_add(1);
+        self.open_defaults = self.open_defaults.saturating_add(1);
+        self.last_default_at = now;
+    }
+
+    /// A seized loan of `principal` left the books, with `shortfall` never recovered.
+    pub fn record_resolution(&mut self, principal: u64, shortfall: u64) -> Result<()> {
+        self.open_defaults = self.open_defaults.saturating_sub(1);
+        self.total_shortfall = self.total_shortfall
+            .checked_add(shortfall)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.record_repayment(principal)
+    }
+}
+
+/// Oracle-signed Merkle root of an integrator's bookings; hosts mint their leaf
+/// with mint_booking_from_root until the root expires.
+#[account]
+#[derive(InitSpace)]
+pub struct BookingBatch {
+    pub integrator_config: Pubkey, // Integrator that posted (and vouches for) the batch
+    pub batch_id: u64,             // Integrator-chosen id, part of the seeds
+    pub oracle: Pubkey,            // Oracle that signed the root
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub claimed_count: u32,
+    pub posted_at: i64,
+    pub expires_at: i64,           // No mints after this; the integrator can then close it
+    pub claimed: [u8; ROOT_BITMAP_BYTES], // One bit per leaf index
+    pub bump: u8,
+}
+
+impl BookingBatch {
+    /// Marks leaf `index` as minted; fails if it already was.
+    pub fn claim(&mut self, index: u32) -> Result<()> {
+        let (byte, mask) = ((index / 8) as usize, 1u8 << (index % 8));
+        let bits = self.claimed.get_mut(byte).ok_or(ErrorCode::InvalidMerkleProof)?;
+        require!(*bits & mask == 0, ErrorCode::LeafAlreadyClaimed);
+        *bits |= mask;
+        self.claimed_count = self.claimed_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Price feed registered for one currency, quoted in USD.
+#[account]
+#[derive(InitSpace)]
+pub struct CurrencyFeed {
+    pub currency: [u8; 3],        // ISO 4217 code, e.g. "EUR"
+    pub feed: Pubkey,             // Pyth PriceUpdateV2 (or MockPriceFeed) account
+    pub feed_id: [u8; 32],        // Pyth feed id the account must carry
+    pub max_staleness: i64,       // Oldest accepted publish_time, in seconds
+    pub max_confidence_bps: u16,  // Widest accepted confidence band, relative to the price
+    pub bump: u8,
+}
+
+/// Admin-written price with the Pyth `PriceUpdateV2` layout, for localnet and tests.
+#[account]
+#[derive(InitSpace)]
+pub struct MockPriceFeed {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Copy, PartialEq, Debug)]
+pub enum ObligationStatus {
+    Active,    // Minted or funded, debt not yet cleared
+    Repaid,    // Settled by the host, NFT released
+    Defaulted, // Seized by liquidation, redeemable by the host, then auctioned
+    Recovered, // Seized NFT sold, shortfall absorbed by the reserve / pool
+    WrittenOff, // Unsold debt realized as a loss by the reserve / pool
+}
+
+/// Accounts that carry a layout version and can be migrated in place.
+pub trait Versioned: Sized {
+    const VERSION: u8;
+
+    fn version(&self) -> u8;
+
+    /// Fills defaults for every field added after the stored version, then stamps VERSION.
+    fn upgrade(&mut self);
+
+    /// Decodes `data` (discriminator stripped) if it is still in the pre-versioning layout
+    /// and that layout is not a prefix of the current one. `None` otherwise.
+    fn decode_baseline(_data: &[u8]) -> Option<Result<Self>> {
+        None
+    }
+}
+
+/// Reads `data` as the baseline layout `L` when it is exactly `L`'s size.
+fn decode_legacy<L, T>(data: &[u8]) -> Option<Result<T>>
+where
+    L: AnchorDeserialize + Space,
+    T: From<L>,
+{
+    (data.len() == L::INIT_SPACE).then(|| {
+        L::deserialize(&mut &data[..])
+            .map(T::from)
+            .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize))
+    })
+}
+
+impl Versioned for BookingObligation {
+    const VERSION: u8 = OBLIGATION_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn decode_baseline(data: &[u8]) -> Option<Result<Self>> {
+        decode_legacy::<LegacyBookingObligation, _>(data)
+    }
+    fn upgrade(&mut self) {
+        if self.version < 4 {
+            // Loans funded before configurable grace periods keep the old fixed 7 days
+            self.grace_period = DEFAULT_GRACE_PERIOD;
+        }
+        self.version = OBLIGATION_VERSION;
+    }
+}
+
+impl Versioned for Position {
+    const VERSION: u8 = POSITION_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) {
+        // v2: yield_debt = 0 entitles the position to everything accrued since the pool's v2 upgrade
+        if self.version < 3 {
+            // No loss had been realized before the loss index existed
+            self.loss_index = YIELD_PRECISION;
+        }
+        self.version = POSITION_VERSION;
+    }
+}
+
+impl Versioned for PoolVault {
+    const VERSION: u8 = POOL_VAULT_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn decode_baseline(data: &[u8]) -> Option<Result<Self>> {
+        decode_legacy::<LegacyPoolVault, _>(data)
+    }
+    fn upgrade(&mut self) {
+        if self.version < 2 {
+            // Before yield accounting every tracked dollar was LP principal
+            self.total_deposits = self.total_liquidity_tracked;
+        }
+        if self.version < 3 {
+            self.grace_period = DEFAULT_GRACE_PERIOD;
+        }
+        if self.version < 4 {
+            self.loss_index = YIELD_PRECISION;
+        }
+        self.version = POOL_VAULT_VERSION;
+    }
+}
+
+impl Versioned for IntegratorConfig {
+    const VERSION: u8 = INTEGRATOR_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) { self.version = INTEGRATOR_VERSION; }
+}
+
+impl Versioned for SupportedToken {
+    const VERSION: u8 = SUPPORTED_TOKEN_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) {
+        if self.version < 2 {
+            self.loss_index = YIELD_PRECISION;
+        }
+        if self.version < 3 {
+            // Every token listed before price feeds was a USD stablecoin
+            self.currency = USD;
+        }
+        self.version = SUPPORTED_TOKEN_VERSION;
+    }
+}
+
+impl Versioned for IntegratorBond {
+    const VERSION: u8 = INTEGRATOR_BOND_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) { self.version = INTEGRATOR_BOND_VERSION; }
+}
+
+impl Versioned for HostProfile {
+    const VERSION: u8 = HOST_PROFILE_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) { self.version = HOST_PROFILE_VERSION; }
+}
+
+impl Versioned for Property {
+    const VERSION: u8 = PROPERTY_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) { self.version = PROPERTY_VERSION; }
+}
+
+impl Versioned for ProtocolConfig {
+    const VERSION: u8 = PROTOCOL_CONFIG_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) { self.version = PROTOCOL_CONFIG_VERSION; }
+}
+
+impl Versioned for ReserveFund {
+    const VERSION: u8 = RESERVE_FUND_VERSION;
+    fn version(&self) -> u8 { self.version }
+    fn upgrade(&mut self) { self.version = RESERVE_FUND_VERSION; }
+}
+
+// --- Legacy Layouts ---
+// Exact pre-versioning (deployed baseline) layouts whose fields were later inserted
+// ahead of `bump`, so they cannot be read as a zero-padded current layout. Position,
+// IntegratorConfig and SupportedToken baselines are prefixes of their current layouts.
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyBookingObligation {
+    #[max_len(32)]
+    pub booking_id: String,
+    pub booking_value: u64,
+    pub max_principal: u64,
+    pub borrowed_amount: u64,
+    pub start_date: i64,
+    pub end_date: i64,
+    pub host_wallet: Pubkey,
+    pub integrator_wallet: Pubkey,
+    pub nft_mint: Pubkey,
+    pub is_locked: bool,
+    pub is_settled: bool,
+    pub tier: ProfitTier,
+    pub bump: u8,
+}
+
+impl From<LegacyBookingObligation> for BookingObligation {
+    fn from(legacy: LegacyBookingObligation) -> Self {
+        Self {
+            booking_id: legacy.booking_id,
+            booking_value: legacy.booking_value,
+            max_principal: legacy.max_principal,
+            borrowed_amount: legacy.borrowed_amount,
+            start_date: legacy.start_date,
+            end_date: legacy.end_date,
+            host_wallet: legacy.host_wallet,
+            integrator_wallet: legacy.integrator_wallet,
+            nft_mint: legacy.nft_mint,
+            is_locked: legacy.is_locked,
+            is_settled: legacy.is_settled,
+            tier: legacy.tier,
+            // Before liquidations a settled booking could only have been repaid
+            status: if legacy.is_settled { ObligationStatus::Repaid } else { ObligationStatus::Active },
+            defaulted_at: 0,
+            recovered_amount: 0,
+            reserve_covered: 0,
+            bond_slashed: 0,
+            rate_bps: 0,
+            bump: legacy.bump,
+            version: 0,
+            payout_reference: [0; 32],
+            liquidator: Pubkey::default(),
+            bounty_paid: 0,
+            bounty_owed: 0,
+            grace_period: 0,
+            redeem_deadline: 0,
+            loan_mint: Pubkey::default(),
+            booking_currency: [0; 3],
+            property: Pubkey::default(),
+            default_cured: false,
+            reserve_slashed: 0,
+            integrator_escrowed: 0,
+            _reserved: [0; OBLIGATION_RESERVED_BYTES],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyPoolVault {
+    pub total_liquidity_tracked: u64,
+    pub bump: u8,
+}
+
+impl From<LegacyPoolVault> for PoolVault {
+    fn from(legacy: LegacyPoolVault) -> Self {
+        Self {
+            total_liquidity_tracked: legacy.total_liquidity_tracked,
+            outstanding_principal: 0,
+            // The baseline had no rate model: start on the default curve, as a fresh pool does
+            rate_model: RateModel {
+                base_rate_bps: DEFAULT_BASE_RATE_BPS,
+                slope1_bps: DEFAULT_SLOPE1_BPS,
+                kink_bps: DEFAULT_KINK_BPS,
+                slope2_bps: DEFAULT_SLOPE2_BPS,
+            },
+            bump: legacy.bump,
+            version: 0,
+            total_deposits: 0,
+            total_yield_earned: 0,
+            acc_yield_per_share: 0,
+            grace_period: 0,
+            tier_grace_periods: [0; 4],
+            loss_index: 0,
+            total_losses: 0,
+            base_mint: Pubkey::default(),
+            _reserved: [0; ACCOUNT_RESERVED_BYTES - 56],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVE: RateModel = RateModel { base_rate_bps: 200, slope1_bps: 400, kink_bps: 8_000, slope2_bps: 6_000 };
+
+    #[test]
+    fn rate_climbs_gently_to_the_kink() {
+        assert_eq!(CURVE.borrow_rate_bps(0).unwrap(), 200);
+        assert_eq!(CURVE.borrow_rate_bps(4_000).unwrap(), 400);
+        assert_eq!(CURVE.borrow_rate_bps(8_000).unwrap(), 600);
+    }
+
+    #[test]
+    fn rate_climbs_steeply_past_the_kink() {
+        assert_eq!(CURVE.borrow_rate_bps(9_000).unwrap(), 3_600);
+        assert_eq!(CURVE.borrow_rate_bps(10_000).unwrap(), 6_600);
+        // Utilization is clamped at 100%
+        assert_eq!(CURVE.borrow_rate_bps(25_000).unwrap(), 6_600);
+    }
+
+    #[test]
+    fn degenerate_kinks_stay_finite() {
+        let no_kink = RateModel { kink_bps: 0, ..CURVE };
+        assert_eq!(no_kink.borrow_rate_bps(0).unwrap(), 200);
+        assert_eq!(no_kink.borrow_rate_bps(5_000).unwrap(), 200 + 400 + 3_000);
+        let all_kink = RateModel { kink_bps: 10_000, ..CURVE };
+        assert_eq!(all_kink.borrow_rate_bps(10_000).unwrap(), 600);
+        assert!(RateModel { kink_bps: 10_001, ..CURVE }.validate().is_err());
+    }
+
+    #[test]
+    fn exposure_caps_take_the_tighter_of_absolute_and_relative() {
+        let limits = ExposureLimits {
+            max_booking_principal: 5_000,
+            max_booking_bps: 1_000, // 10% of pool assets
+            max_host_principal: 20_000,
+            ..ExposureLimits::default()
+        };
+        // 10% of 100k is 10k, so the 5k absolute cap binds
+        assert!(limits.check(100_000, 5_000, 5_000, 5_000).is_ok());
+        assert_eq!(
+            limits.check(100_000, 5_001, 5_001, 5_001),
+            Err(ErrorCode::BookingExposureExceeded.into())
+        );
+        // 10% of 30k is 3k, so the relative cap binds
+        assert_eq!(
+            limits.check(30_000, 4_000, 4_000, 4_000),
+            Err(ErrorCode::BookingExposureExceeded.into())
+        );
+        assert_eq!(
+            limits.check(100_000, 1_000, 20_001, 1_000),
+            Err(ErrorCode::HostExposureExceeded.into())
+        );
+    }
+
+    #[test]
+    fn disabled_exposure_caps_never_bind() {
+        assert!(ExposureLimits::default().check(0, u64::MAX, u64::MAX, u64::MAX).is_ok());
+
+        let integrator_only = ExposureLimits { max_integrator_bps: 5_000, ..ExposureLimits::default() };
+        assert!(integrator_only.check(10_000, 9_000, 9_000, 5_000).is_ok());
+        assert_eq!(
+            integrator_only.check(10_000, 1, 1, 5_001),
+            Err(ErrorCode::IntegratorExposureExceeded.into())
+        );
+        assert!(ExposureLimits { max_host_bps: 10_001, ..ExposureLimits::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn param_changes_are_validated_at_queue_time() {
+        assert!(ParamChange::ReserveBps { reserve_bps: 10_000 }.validate().is_ok());
+        assert_eq!(
+            ParamChange::ProtocolFeeBps { fee_bps: 10_001 }.validate(),
+            Err(ErrorCode::InvalidBps.into())
+        );
+        assert_eq!(
+            ParamChange::GracePeriods { pool: 0, tiers: [0, MAX_GRACE_PERIOD + 1, 0, 0] }.validate(),
+            Err(ErrorCode::InvalidGracePeriod.into())
+        );
+        assert_eq!(
+            ParamChange::TimelockDelay { delay: -1 }.validate(),
+            Err(ErrorCode::InvalidAmount.into())
+        );
+        assert_eq!(
+            ParamChange::Oracle { oracle: Pubkey::default() }.validate(),
+            Err(ErrorCode::InvalidOracleKey.into())
+        );
+        let terms = RedemptionTerms { window: MAX_REDEMPTION_WINDOW + 1, penalty_bps: 0 };
+        assert!(ParamChange::Redemption { terms }.validate().is_err());
+        let rate_model = RateModel { kink_bps: 10_001, ..CURVE };
+        assert!(ParamChange::RateModel { rate_model }.validate().is_err());
+    }
+
+    #[test]
+    fn grace_period_prefers_integrator_then_tier_then_pool() {
+        let mut pool = PoolVault::from(LegacyPoolVault { total_liquidity_tracked: 0, bump: 0 });
+        pool.grace_period = 7 * 86_400;
+        pool.tier_grace_periods[ProfitTier::SixMonth as usize] = 14 * 86_400;
+
+        assert_eq!(pool.grace_period_for(ProfitTier::OneMonth, 0), 7 * 86_400);
+        assert_eq!(pool.grace_period_for(ProfitTier::SixMonth, 0), 14 * 86_400);
+        assert_eq!(pool.grace_period_for(ProfitTier::SixMonth, 3 * 86_400), 3 * 86_400);
+        assert_eq!(pool.grace_period_for(ProfitTier::OneMonth, 3 * 86_400), 3 * 86_400);
+    }
+
+    #[test]
+    fn escrow_leftover_is_refunded_pro_rata_to_funders() {
+        let mut obligation =
+            BookingObligation::try_deserialize_unchecked(&mut vec![0u8; BookingObligation::SIZE].as_slice()).unwrap();
+        // Host-only escrow: the integrator gets nothing back
+        assert_eq!(obligation.integrator_escrow_share(400, 1_000).unwrap(), 0);
+
+        obligation.integrator_escrowed = 250;
+        assert_eq!(obligation.integrator_escrow_share(400, 1_000).unwrap(), 100);
+        assert_eq!(obligation.integrator_escrow_share(1_000, 1_000).unwrap(), 250);
+        assert_eq!(obligation.integrator_escrow_share(0, 1_000).unwrap(), 0);
+        assert_eq!(obligation.integrator_escrow_share(0, 0).unwrap(), 0);
+    }
+}
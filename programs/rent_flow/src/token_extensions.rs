@@ -0,0 +1,151 @@
+//! Token-2022 awareness for lending mints: which extensions are safe to list,
+//! and what transfer fees do to the amounts the books record.
+//!
+//! Rule of thumb: whoever pays into the protocol bears the fee (amounts owed are
+//! grossed up so the protocol receives exactly what it records), and whoever is
+//! paid out bears the fee on what they receive. Deposits are credited net.
+use anchor_lang::prelude::*;
+use spl_token_2022::extension::{
+    default_account_state::DefaultAccountState,
+    transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use spl_token_2022::state::AccountState;
+use crate::error::ErrorCode;
+
+/// Extensions that let someone other than the holder move or freeze pool funds.
+pub const UNSAFE_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::PermanentDelegate,        // Issuer can transfer or burn out of the vault
+    ExtensionType::NonTransferable,          // Vault could never pay anyone back
+    ExtensionType::TransferHook,             // Issuer's program can veto (or reroute) any pool transfer
+    ExtensionType::Pausable,                 // Issuer can halt every transfer in and out of the vault
+    ExtensionType::ConfidentialTransferMint, // Balances the books cannot see
+];
+
+/// Fails if `mint` carries an extension the pool cannot safely hold.
+pub fn validate_lending_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(()); // Plain SPL mints have no extensions
+    }
+    check_mint_extensions(&mint.try_borrow_data()?)
+}
+
+fn check_mint_extensions(data: &[u8]) -> Result<()> {
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data)?;
+    for extension in state.get_extension_types()? {
+        require!(!UNSAFE_MINT_EXTENSIONS.contains(&extension), ErrorCode::UnsupportedMintExtension);
+    }
+    // New accounts born frozen: every vault and ATA would need the issuer's thaw first
+    if let Ok(default_state) = state.get_extension::<DefaultAccountState>() {
+        require!(
+            default_state.state != AccountState::Frozen as u8,
+            ErrorCode::UnsupportedMintExtension
+        );
+    }
+    Ok(())
+}
+
+/// What the recipient gets from a transfer of `amount` this epoch.
+pub fn net_of_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let fee = match fee_config(mint)? {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+        None => 0,
+    };
+    amount.checked_sub(fee).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+/// What must be sent this epoch so the recipient gets exactly `amount`.
+pub fn gross_up(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let fee = match fee_config(mint)? {
+        Some(config) if amount > 0 => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+        _ => 0,
+    };
+    amount.checked_add(fee).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+/// Fees withheld in a Token-2022 account; such an account cannot be closed
+/// until the withdraw authority harvests them.
+pub fn withheld_fees(token_account: &AccountInfo) -> Result<u64> {
+    if *token_account.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = token_account.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map(|withheld| u64::from(withheld.withheld_amount))
+        .unwrap_or(0))
+}
+
+fn fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token_2022::extension::{
+        pausable::PausableConfig, permanent_delegate::PermanentDelegate, transfer_hook::TransferHook,
+        BaseStateWithExtensionsMut, StateWithExtensionsMut,
+    };
+    use spl_token_2022::state::Mint;
+
+    /// A Token-2022 mint account with `extensions`, each set up by `init`.
+    fn mint_with(extensions: &[ExtensionType], init: impl FnOnce(&mut StateWithExtensionsMut<Mint>)) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap();
+        let mut data = vec![0; len];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        init(&mut state);
+        state.base = Mint { decimals: 6, is_initialized: true, ..Mint::default() };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    fn rejected(data: &[u8]) -> bool {
+        check_mint_extensions(data) == Err(ErrorCode::UnsupportedMintExtension.into())
+    }
+
+    #[test]
+    fn plain_and_fee_bearing_mints_are_accepted() {
+        assert!(check_mint_extensions(&mint_with(&[], |_| {})).is_ok());
+        let fee_mint = mint_with(&[ExtensionType::TransferFeeConfig], |state| {
+            state.init_extension::<TransferFeeConfig>(true).unwrap();
+        });
+        assert!(check_mint_extensions(&fee_mint).is_ok());
+    }
+
+    #[test]
+    fn issuer_controlled_mints_are_rejected() {
+        assert!(rejected(&mint_with(&[ExtensionType::PermanentDelegate], |state| {
+            state.init_extension::<PermanentDelegate>(true).unwrap();
+        })));
+        assert!(rejected(&mint_with(&[ExtensionType::TransferHook], |state| {
+            state.init_extension::<TransferHook>(true).unwrap();
+        })));
+        assert!(rejected(&mint_with(&[ExtensionType::Pausable], |state| {
+            state.init_extension::<PausableConfig>(true).unwrap();
+        })));
+    }
+
+    #[test]
+    fn only_frozen_default_account_state_is_rejected() {
+        let with_default = |frozen: bool| {
+            mint_with(&[ExtensionType::DefaultAccountState], |state| {
+                let extension = state.init_extension::<DefaultAccountState>(true).unwrap();
+                extension.state = if frozen { AccountState::Frozen } else { AccountState::Initialized } as u8;
+            })
+        };
+        assert!(check_mint_extensions(&with_default(false)).is_ok());
+        assert!(rejected(&with_default(true)));
+    }
+}
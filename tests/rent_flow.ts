@@ -3,8 +3,10 @@ import { Program } from "@coral-xyz/anchor";
 import { RentFlow } from "../target/types/rent_flow";
 import { 
   PublicKey, Keypair, SystemProgram, 
-  SYSVAR_INSTRUCTIONS_PUBKEY, LAMPORTS_PER_SOL, Ed25519Program 
+  SYSVAR_INSTRUCTIONS_PUBKEY, LAMPORTS_PER_SOL, Ed25519Program,
+  ComputeBudgetProgram
 } from "@solana/web3.js";
+import { createHash } from "crypto";
 import { 
   TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync, 
   ASSOCIATED_TOKEN_PROGRAM_ID, createMint, mintTo,
@@ -21,7 +23,7 @@ describe("rent_flow_proving_grounds", () => {
   // 🏁 SHARED STATE (Hoisted to top-level scope)
   const host = Keypair.generate();
   const nftMint = Keypair.generate(); 
-  const oracle = Keypair.generate();   
+  const oracle = (provider.wallet as anchor.Wallet).payer; // protocol_config.oracle starts as the admin
   const integrationWallet = Keypair.generate(); 
   const investorWallet = Keypair.generate().publicKey;
 
@@ -32,8 +34,85 @@ describe("rent_flow_proving_grounds", () => {
   let vaultNftAta: PublicKey;
   let usdcMint: Keypair;
   let hostUsdcAta: PublicKey;
-  let vaultUsdcAta: PublicKey;
+  let treasuryUsdcAta: PublicKey;
   let poolNftAta: PublicKey;
+  let protocolConfigPda: PublicKey;
+  let reserveFundPda: PublicKey;
+  let reserveUsdcAta: PublicKey;
+  let integratorBondPda: PublicKey;
+  let bondUsdcAta: PublicKey;
+  let adminUsdcAta: PublicKey;
+  let tokenVault: PublicKey;
+  let supportedTokenPda: PublicKey;
+  let hostProfilePda: PublicKey;
+  let lpPositionPda: PublicKey;
+
+  // Root-minted booking, reused by the later lifecycle tests
+  const rootNftMint = Keypair.generate();
+  let rootObligationPda: PublicKey;
+
+  // 🧰 HELPERS
+  const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+  // A v1 proof for `host` and its signed bytes: "rent_flow:booking:v1" || borsh(BookingProof)
+  const bookingProofV1 = (bookingId: string, nonce: number) => {
+    const now = Math.floor(Date.now() / 1000);
+    const proof = {
+      bookingId,
+      amount: new anchor.BN(1000000),
+      startDate: new anchor.BN(now + 5000),
+      endDate: new anchor.BN(now + 10000),
+      hostWallet: host.publicKey,
+      oraclePubkey: oracle.publicKey,
+      tierIndex: 0,
+      investorWallet: investorWallet,
+      issuedAt: new anchor.BN(now - 10),
+      expiresAt: new anchor.BN(now + 600),
+      nonce: new anchor.BN(nonce),
+    };
+    const bookingIdLen = Buffer.alloc(4);
+    bookingIdLen.writeUInt32LE(Buffer.byteLength(bookingId), 0);
+    const message = Buffer.concat([
+      Buffer.from("rent_flow:booking:v1"),
+      bookingIdLen, Buffer.from(bookingId),
+      proof.amount.toArrayLike(Buffer, "le", 8),
+      proof.startDate.toArrayLike(Buffer, "le", 8),
+      proof.endDate.toArrayLike(Buffer, "le", 8),
+      host.publicKey.toBuffer(),
+      oracle.publicKey.toBuffer(),
+      Buffer.from([proof.tierIndex]),
+      investorWallet.toBuffer(),
+      proof.issuedAt.toArrayLike(Buffer, "le", 8),
+      proof.expiresAt.toArrayLike(Buffer, "le", 8),
+      proof.nonce.toArrayLike(Buffer, "le", 8),
+    ]);
+    return { envelope: { version: 1, proof, extensions: Buffer.alloc(0) }, message };
+  };
+
+  const oracleSignature = (message: Buffer) =>
+    Ed25519Program.createInstructionWithPublicKey({
+      publicKey: oracle.publicKey.toBytes(),
+      message: message,
+      signature: nacl.sign.detached(message, oracle.secretKey),
+    });
+
+  const noncePageFor = (nonce: number) => PublicKey.findProgramAddressSync(
+    [Buffer.from("nonce_page"), oracle.publicKey.toBuffer(), new anchor.BN(Math.floor(nonce / 4096)).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+
+  // Fails unless `tx` is rejected with the program error `code`
+  const expectRejected = async (tx: Promise<unknown>, code: string) => {
+    let thrown: any = null;
+    try {
+      await tx;
+    } catch (err: any) {
+      thrown = err;
+    }
+    expect(thrown, `Should have thrown ${code}`).to.not.be.null;
+    const logs = thrown.logs ? thrown.logs.join("\n") : "";
+    expect(thrown.toString() + logs).to.include(code);
+  };
 
   before(async () => {
     // Airdrop SOL to host for transaction fees
@@ -60,6 +139,32 @@ describe("rent_flow_proving_grounds", () => {
       program.programId
     );
 
+    [protocolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    );
+
+    [reserveFundPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve_fund")],
+      program.programId
+    );
+
+    [integratorBondPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("integrator_bond"), integrationConfigPda.toBuffer()],
+      program.programId
+    );
+
+    [rootObligationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("obligation"), rootNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    [hostProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("host_profile"), host.publicKey.toBuffer()],
+      program.programId
+    );
+
+
     hostNftAta = getAssociatedTokenAddressSync(
       nftMint.publicKey, host.publicKey, false, TOKEN_2022_PROGRAM_ID
     );
@@ -81,6 +186,16 @@ describe("rent_flow_proving_grounds", () => {
       TOKEN_PROGRAM_ID
     );
 
+    [lpPositionPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), provider.wallet.publicKey.toBuffer(), usdcMint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    [supportedTokenPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), usdcMint.publicKey.toBuffer()],
+      program.programId
+    );
+
     hostUsdcAta = getAssociatedTokenAddressSync(
       usdcMint.publicKey, host.publicKey, false, TOKEN_PROGRAM_ID
     );
@@ -95,48 +210,57 @@ describe("rent_flow_proving_grounds", () => {
         );
     } catch (e) {}
 
-    vaultUsdcAta = getAssociatedTokenAddressSync(
-      usdcMint.publicKey, obligationPda, true, TOKEN_PROGRAM_ID
+    reserveUsdcAta = getAssociatedTokenAddressSync(
+      usdcMint.publicKey, reserveFundPda, true, TOKEN_PROGRAM_ID
+    );
+
+    treasuryUsdcAta = getAssociatedTokenAddressSync(
+      usdcMint.publicKey, protocolConfigPda, true, TOKEN_PROGRAM_ID
     );
-    try {
-        await createAssociatedTokenAccount(
-            provider.connection,
-            host,
-            usdcMint.publicKey,
-            obligationPda,
-            undefined,
-            TOKEN_PROGRAM_ID,
-            ASSOCIATED_TOKEN_PROGRAM_ID,
-            true // allowOffCurve
-        );
-    } catch (e) {}
 
-    // Mint some USDC to the vault so it can fund the host
+    // Mint some USDC to the Host so they can repay (10 USDC)
     await mintTo(
       provider.connection,
       host,
       usdcMint.publicKey,
-      vaultUsdcAta,
+      hostUsdcAta,
       host.publicKey,
-      1000000000, // 1000 USDC
+      10000000,
       [],
       undefined,
       TOKEN_PROGRAM_ID
     );
 
-    // Mint some USDC to the Host so they can repay (10 USDC)
+    // Fund the integrator authority (provider wallet) so it can post its bond
+    adminUsdcAta = await createAssociatedTokenAccount(
+      provider.connection,
+      host,
+      usdcMint.publicKey,
+      provider.wallet.publicKey,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
     await mintTo(
       provider.connection,
       host,
       usdcMint.publicKey,
-      hostUsdcAta,
+      adminUsdcAta,
       host.publicKey,
-      10000000,
+      100000000, // 100 USDC
       [],
       undefined,
       TOKEN_PROGRAM_ID
     );
 
+    bondUsdcAta = getAssociatedTokenAddressSync(
+      usdcMint.publicKey, integratorBondPda, true, TOKEN_PROGRAM_ID
+    );
+
+    // USDC lending pool cash: the SupportedToken's vault, created by addToken
+    tokenVault = getAssociatedTokenAddressSync(
+      usdcMint.publicKey, supportedTokenPda, true, TOKEN_PROGRAM_ID
+    );
+
     poolNftAta = getAssociatedTokenAddressSync(
       nftMint.publicKey, poolVaultPda, true, TOKEN_2022_PROGRAM_ID
     );
@@ -168,23 +292,166 @@ describe("rent_flow_proving_grounds", () => {
       .rpc();
   });
 
+  it("0b. Setup: Initialize Protocol & Reserve Fund", async () => {
+    await program.methods
+      .initializeProtocol(1000) // 10% of interest + penalties -> reserve
+      .accounts({
+        admin: provider.wallet.publicKey,
+        protocolConfig: protocolConfigPda,
+        reserveFund: reserveFundPda,
+        usdcMint: usdcMint.publicKey,
+        reserveUsdcAta: reserveUsdcAta,
+        treasuryUsdcAta: treasuryUsdcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("0c. Setup: Bond & Activate Integrator", async () => {
+    await program.methods
+      .postIntegratorBond(new anchor.BN(50000000)) // 50 USDC
+      .accounts({
+        authority: provider.wallet.publicKey,
+        integrationConfig: integrationConfigPda,
+        integrationWallet: integrationWallet.publicKey,
+        integratorBond: integratorBondPda,
+        usdcMint: usdcMint.publicKey,
+        authorityUsdcAta: adminUsdcAta,
+        bondUsdcAta: bondUsdcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setIntegratorStatus(true)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        protocolConfig: protocolConfigPda,
+        integrationConfig: integrationConfigPda,
+        integrationWallet: integrationWallet.publicKey,
+        integratorBond: integratorBondPda,
+      })
+      .rpc();
+
+    const config = await program.account.integratorConfig.fetch(integrationConfigPda);
+    expect(config.isActive).to.be.true;
+  });
+
+  it("0d. Setup: List USDC as Supported Token", async () => {
+    await program.methods
+      .addToken(7500, Array.from(Buffer.from("USD"))) // 75% base LTV, a USD stablecoin
+      .accounts({
+        admin: provider.wallet.publicKey,
+        protocolConfig: protocolConfigPda,
+        mint: usdcMint.publicKey,
+        supportedToken: supportedTokenPda,
+        tokenVault: tokenVault,
+        reserveFund: reserveFundPda,
+        treasuryTokenAta: treasuryUsdcAta,
+        reserveTokenAta: reserveUsdcAta,
+        flexibleTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("0e. Setup: LP Deposits Liquidity", async () => {
+    await program.methods
+      .depositLiquidity(new anchor.BN(20000000)) // 20 USDC
+      .accounts({
+        investor: provider.wallet.publicKey,
+        position: lpPositionPda,
+        supportedToken: supportedTokenPda,
+        protocolConfig: protocolConfigPda,
+        investorTokenAta: adminUsdcAta,
+        tokenVault: tokenVault,
+        mint: usdcMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const pool = await program.account.supportedToken.fetch(supportedTokenPda);
+    expect(pool.totalLiquidityTracked.toNumber()).to.equal(20000000);
+  });
+
+  it("0f. Setup: Register an EUR Price Feed (Mock)", async () => {
+    const feedId = Array.from(Buffer.alloc(32, 0xe0));
+    const [mockFeedPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mock_price_feed"), Buffer.from(feedId)],
+      program.programId
+    );
+    const [eurFeedPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("currency_feed"), Buffer.from("EUR")],
+      program.programId
+    );
+
+    // EUR/USD 1.08 +/- 0.001
+    await program.methods
+      .setMockPrice(feedId, new anchor.BN(108000), new anchor.BN(100), -5)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        protocolConfig: protocolConfigPda,
+        mockPriceFeed: mockFeedPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setCurrencyFeed(Array.from(Buffer.from("EUR")), feedId, new anchor.BN(60), 100)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        protocolConfig: protocolConfigPda,
+        currencyFeed: eurFeedPda,
+        feed: mockFeedPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const eur = await program.account.currencyFeed.fetch(eurFeedPda);
+    expect(eur.feed.toBase58()).to.equal(mockFeedPda.toBase58());
+  });
+
   it("1. Transformation: Mint Booking RWA", async () => {
     const bookingId = "GT3-911-PRO";
     const amount = new anchor.BN(1000000);
-    const startDate = new anchor.BN(Math.floor(Date.now() / 1000) + 5000);
-    const endDate = new anchor.BN(Math.floor(Date.now() / 1000) + 10000);
+    const now = Math.floor(Date.now() / 1000);
+    const startDate = new anchor.BN(now + 5000);
+    const endDate = new anchor.BN(now + 10000);
+    const issuedAt = new anchor.BN(now - 10);
+    const expiresAt = new anchor.BN(now + 600);
+    const nonce = new anchor.BN(1);
+    const tierIndex = 0; // OneMonth: the booking lasts under 31 days
 
     const bookingIdBuffer = Buffer.from(bookingId);
     const bookingIdLen = Buffer.alloc(4);
     bookingIdLen.writeUInt32LE(bookingIdBuffer.length, 0);
 
+    // "rent_flow:booking:v1" || borsh(BookingProof)
     const message = Buffer.concat([
+      Buffer.from("rent_flow:booking:v1"),
       bookingIdLen, bookingIdBuffer,
       amount.toArrayLike(Buffer, "le", 8),
       startDate.toArrayLike(Buffer, "le", 8),
       endDate.toArrayLike(Buffer, "le", 8),
       host.publicKey.toBuffer(),
+      oracle.publicKey.toBuffer(),
+      Buffer.from([tierIndex]),
+      investorWallet.toBuffer(),
+      issuedAt.toArrayLike(Buffer, "le", 8),
+      expiresAt.toArrayLike(Buffer, "le", 8),
+      nonce.toArrayLike(Buffer, "le", 8),
     ]);
+    const [noncePagePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nonce_page"), oracle.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
 
     const signature = nacl.sign.detached(message, oracle.secretKey);
     const signatureInstruction = Ed25519Program.createInstructionWithPublicKey({
@@ -195,19 +462,28 @@ describe("rent_flow_proving_grounds", () => {
 
     await program.methods
       .mintBooking({
-        bookingId, amount, startDate, endDate,
-        hostWallet: host.publicKey,
-        oraclePubkey: oracle.publicKey,
-        tierIndex: 2, // SixMonth
-        investorWallet: investorWallet,
+        version: 1, // v1 envelope: no extension area
+        proof: {
+          bookingId, amount, startDate, endDate,
+          hostWallet: host.publicKey,
+          oraclePubkey: oracle.publicKey,
+          tierIndex,
+          investorWallet: investorWallet,
+          issuedAt, expiresAt, nonce,
+        },
+        extensions: Buffer.alloc(0),
       })
       .accounts({
         host: host.publicKey,
         integrationConfig: integrationConfigPda,
         integrationWallet: integrationWallet.publicKey,
+        integratorBond: integratorBondPda,
+        protocolConfig: protocolConfigPda,
         nftMint: nftMint.publicKey,
         hostAta: hostNftAta,
         bookingObligation: obligationPda,
+        noncePage: noncePagePda,
+        property: null, // Proof names no listing
         sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         token2022Program: TOKEN_2022_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -218,6 +494,45 @@ describe("rent_flow_proving_grounds", () => {
       .rpc();
   });
 
+  it("1b. Safety Sensor: Reject Zero-Amount Deposit", async () => {
+    await expectRejected(
+      program.methods
+        .depositCollateral(new anchor.BN(0))
+        .accounts({
+          host: host.publicKey,
+          obligation: obligationPda,
+          nftMint: nftMint.publicKey,
+          loanMint: usdcMint.publicKey,
+          hostNftAta: hostNftAta,
+          hostTokenAta: hostUsdcAta,
+          vaultNftAta: vaultNftAta,
+          poolVault: poolVaultPda,
+          tokenVault: tokenVault,
+          integrationConfig: integrationConfigPda,
+          supportedToken: supportedTokenPda,
+          hostProfile: hostProfilePda,
+          property: null,
+          protocolConfig: protocolConfigPda,
+          bookingCurrencyFeed: null,
+          bookingPriceFeed: null,
+          loanCurrencyFeed: null,
+          loanPriceFeed: null,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([host])
+        .rpc(),
+      "InvalidAmount"
+    );
+
+    // The NFT never left the host
+    const state = await program.account.bookingObligation.fetch(obligationPda);
+    expect(state.isLocked).to.be.false;
+    console.log("✅ Sensor Tripped: Zero-amount deposit blocked.");
+  });
+
 it("2. Transformation: Secure Collateral (Deposit)", async () => {
     // 1. Build the instruction manually to inspect it
     const ix = await program.methods
@@ -226,11 +541,22 @@ it("2. Transformation: Secure Collateral (Deposit)", async () => {
         host: host.publicKey,
         obligation: obligationPda,
         nftMint: nftMint.publicKey,
-        usdcMint: usdcMint.publicKey,
+        loanMint: usdcMint.publicKey,
         hostNftAta: hostNftAta,
-        hostUsdcAta: hostUsdcAta,
+        hostTokenAta: hostUsdcAta,
         vaultNftAta: vaultNftAta,
-        vaultUsdcAta: vaultUsdcAta,
+        poolVault: poolVaultPda,
+        tokenVault: tokenVault,
+        integrationConfig: integrationConfigPda,
+        supportedToken: supportedTokenPda,
+        hostProfile: hostProfilePda,
+        property: null,
+        protocolConfig: protocolConfigPda,
+        // Booking priced in the loan currency: no price feeds needed
+        bookingCurrencyFeed: null,
+        bookingPriceFeed: null,
+        loanCurrencyFeed: null,
+        loanPriceFeed: null,
         token2022Program: TOKEN_2022_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID, // Standard USDC
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -252,11 +578,22 @@ it("2. Transformation: Secure Collateral (Deposit)", async () => {
             host: host.publicKey,
             obligation: obligationPda,
             nftMint: nftMint.publicKey,
-            usdcMint: usdcMint.publicKey,
+            loanMint: usdcMint.publicKey,
             hostNftAta: hostNftAta,
-            hostUsdcAta: hostUsdcAta,
+            hostTokenAta: hostUsdcAta,
             vaultNftAta: vaultNftAta,
-            vaultUsdcAta: vaultUsdcAta,
+            poolVault: poolVaultPda,
+            tokenVault: tokenVault,
+            integrationConfig: integrationConfigPda,
+            supportedToken: supportedTokenPda,
+            hostProfile: hostProfilePda,
+            property: null,
+            protocolConfig: protocolConfigPda,
+            // Booking priced in the loan currency: no price feeds needed
+            bookingCurrencyFeed: null,
+            bookingPriceFeed: null,
+            loanCurrencyFeed: null,
+            loanPriceFeed: null,
             token2022Program: TOKEN_2022_PROGRAM_ID,
             tokenProgram: TOKEN_PROGRAM_ID, // Standard USDC
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -281,11 +618,22 @@ it("2. Transformation: Secure Collateral (Deposit)", async () => {
           host: host.publicKey,
           obligation: obligationPda,
           nftMint: nftMint.publicKey,         
-          usdcMint: usdcMint.publicKey,
+          loanMint: usdcMint.publicKey,
           hostNftAta: hostNftAta,             
-          hostUsdcAta: hostUsdcAta,
+          hostTokenAta: hostUsdcAta,
           vaultNftAta: vaultNftAta,           
-          vaultUsdcAta: vaultUsdcAta,
+          poolVault: poolVaultPda,
+          tokenVault: tokenVault,
+          integrationConfig: integrationConfigPda,
+          supportedToken: supportedTokenPda,
+          hostProfile: hostProfilePda,
+          property: null,
+          protocolConfig: protocolConfigPda,
+          // Booking priced in the loan currency: no price feeds needed
+          bookingCurrencyFeed: null,
+          bookingPriceFeed: null,
+          loanCurrencyFeed: null,
+          loanPriceFeed: null,
           token2022Program: TOKEN_2022_PROGRAM_ID,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -318,6 +666,23 @@ it("2. Transformation: Secure Collateral (Deposit)", async () => {
           vaultNftAta: vaultNftAta,
           poolNftAta: poolNftAta, 
           poolVault: poolVaultPda,
+          protocolConfig: protocolConfigPda,
+          integrationConfig: integrationConfigPda,
+          supportedToken: supportedTokenPda,
+          integratorBond: integratorBondPda,
+          bondMint: usdcMint.publicKey,
+          bondTokenAta: bondUsdcAta,
+          reserveFund: reserveFundPda,
+          reserveBondAta: reserveUsdcAta,
+          loanMint: usdcMint.publicKey,
+          tokenVault: tokenVault,
+          liquidatorTokenAta: getAssociatedTokenAddressSync(
+            usdcMint.publicKey, liquidator.publicKey, false, TOKEN_PROGRAM_ID
+          ),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          hostProfile: hostProfilePda,
+          property: null,
           token2022Program: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -349,9 +714,17 @@ it("2. Transformation: Secure Collateral (Deposit)", async () => {
         nftMint: nftMint.publicKey,
         hostNftAta: hostNftAta,
         vaultNftAta: vaultNftAta,
-        usdcMint: usdcMint.publicKey,
-        hostUsdcAta: hostUsdcAta,
-        vaultUsdcAta: vaultUsdcAta,
+        loanMint: usdcMint.publicKey,
+        hostTokenAta: hostUsdcAta,
+        supportedToken: supportedTokenPda,
+        tokenVault: tokenVault,
+        treasuryTokenAta: treasuryUsdcAta,
+        protocolConfig: protocolConfigPda,
+        reserveFund: reserveFundPda,
+        reserveTokenAta: reserveUsdcAta,
+        integrationConfig: integrationConfigPda,
+        hostProfile: hostProfilePda,
+        property: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         token2022Program: TOKEN_2022_PROGRAM_ID,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
@@ -362,6 +735,459 @@ it("2. Transformation: Secure Collateral (Deposit)", async () => {
     const state = await program.account.bookingObligation.fetch(obligationPda);
     expect(state.isSettled).to.be.true;
     expect(state.isLocked).to.be.false;
+    expect(state.status).to.have.property("repaid");
+
+    const profile = await program.account.hostProfile.fetch(hostProfilePda);
+    expect(profile.earlyCount).to.equal(1); // Settled before end_date
+
+    const reserve = await program.account.reserveFund.fetch(reserveFundPda);
+    expect(reserve.totalContributed.toNumber()).to.be.greaterThan(0);
+
+    // Waterfall: principal back to the pool, the LPs' share of interest + penalty booked as yield
+    const pool = await program.account.supportedToken.fetch(supportedTokenPda);
+    expect(pool.outstandingPrincipal.toNumber()).to.equal(0);
+    expect(pool.totalYieldEarned.toNumber()).to.be.greaterThan(0);
     console.log("✅ Settlement Success: NFT returned to Host.");
   });
-});
\ No newline at end of file
+
+  it("6. Safety Sensor: Guardian Pause Halts Settlement", async () => {
+    const PAUSE_SETTLE = 1 << 2;
+    await program.methods
+      .pause(PAUSE_SETTLE)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        protocolConfig: protocolConfigPda,
+      })
+      .rpc();
+
+    let config = await program.account.protocolConfig.fetch(protocolConfigPda);
+    expect(config.paused).to.equal(PAUSE_SETTLE);
+
+    await program.methods
+      .unpause(PAUSE_SETTLE)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        protocolConfig: protocolConfigPda,
+      })
+      .rpc();
+
+    config = await program.account.protocolConfig.fetch(protocolConfigPda);
+    expect(config.paused).to.equal(0);
+    console.log("✅ Sensor Tripped: Pause bit set and cleared.");
+  });
+
+  it("7. Replay Guard: Root Leaves and Nonces Mint Once", async () => {
+    // Leaf 0 is a fresh booking; leaf 1 reuses nonce 1, already burned by mint_booking in test 1
+    const fresh = bookingProofV1("ROOT-LEAF-0", 20);
+    const replayed = bookingProofV1("ROOT-LEAF-1", 1);
+    const leaves = [fresh, replayed].map(({ message }) => sha256(Buffer.from([0x00]), message));
+    const root = sha256(Buffer.from([0x01]), leaves[0], leaves[1]);
+
+    const batchId = new anchor.BN(1);
+    const leafCount = 2;
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    const [bookingBatchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("booking_batch"), integrationConfigPda.toBuffer(), batchId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // "rent_flow:booking_root:v1" || borsh(BookingRootAttestation)
+    const leafCountBytes = Buffer.alloc(4);
+    leafCountBytes.writeUInt32LE(leafCount, 0);
+    const attestation = Buffer.concat([
+      Buffer.from("rent_flow:booking_root:v1"),
+      integrationConfigPda.toBuffer(),
+      batchId.toArrayLike(Buffer, "le", 8),
+      root,
+      leafCountBytes,
+      expiresAt.toArrayLike(Buffer, "le", 8),
+    ]);
+    await program.methods
+      .postBookingRoot(batchId, Array.from(root), leafCount, expiresAt)
+      .accounts({
+        authority: provider.wallet.publicKey,
+        integrationConfig: integrationConfigPda,
+        integrationWallet: integrationWallet.publicKey,
+        protocolConfig: protocolConfigPda,
+        bookingBatch: bookingBatchPda,
+        sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([oracleSignature(attestation)])
+      .rpc();
+
+    const mintLeaf = (
+      booking: ReturnType<typeof bookingProofV1>,
+      leafIndex: number,
+      sibling: Buffer,
+      nftMint: Keypair,
+    ) => {
+      const [obligation] = PublicKey.findProgramAddressSync(
+        [Buffer.from("obligation"), nftMint.publicKey.toBuffer()],
+        program.programId
+      );
+      return program.methods
+        .mintBookingFromRoot(booking.envelope, leafIndex, [Array.from(sibling)])
+        .accounts({
+          host: host.publicKey,
+          integrationConfig: integrationConfigPda,
+          integrationWallet: integrationWallet.publicKey,
+          integratorBond: integratorBondPda,
+          protocolConfig: protocolConfigPda,
+          bookingBatch: bookingBatchPda,
+          property: null,
+          nftMint: nftMint.publicKey,
+          hostAta: getAssociatedTokenAddressSync(nftMint.publicKey, host.publicKey, false, TOKEN_2022_PROGRAM_ID),
+          bookingObligation: obligation,
+          noncePage: noncePageFor(booking.envelope.proof.nonce.toNumber()),
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([host, nftMint])
+        .rpc();
+    };
+
+    await mintLeaf(fresh, 0, leaves[1], rootNftMint);
+    const batch = await program.account.bookingBatch.fetch(bookingBatchPda);
+    expect(batch.root).to.deep.equal(Array.from(root));
+
+    // Same leaf, new NFT mint: the claimed-leaf bitmap refuses it
+    await expectRejected(mintLeaf(fresh, 0, leaves[1], Keypair.generate()), "LeafAlreadyClaimed");
+    // A valid leaf whose nonce another mint path already burned
+    await expectRejected(mintLeaf(replayed, 1, leaves[0], Keypair.generate()), "NonceAlreadyUsed");
+    console.log("✅ Sensor Tripped: Root leaves and nonces are single-use.");
+  });
+
+  it("8. Replay Guard: Batches Cannot Mint a Nonce Twice", async () => {
+    // One oracle signature over the whole batch: "rent_flow:booking_batch" || sha256(sha256(m_1) || ... )
+    const mintBatch = (nonces: number[]) => {
+      const bookings = nonces.map((nonce) => bookingProofV1(`BATCH-${nonce}`, nonce));
+      const batchMessage = Buffer.concat([
+        Buffer.from("rent_flow:booking_batch"),
+        sha256(...bookings.map(({ message }) => sha256(message))),
+      ]);
+
+      // [nft_mint, host_ata, booking_obligation, nonce_page, property] per booking
+      const remainingAccounts = nonces.flatMap((nonce) => {
+        const [nftMint] = PublicKey.findProgramAddressSync(
+          [Buffer.from("booking_mint"), oracle.publicKey.toBuffer(), new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        const [obligation] = PublicKey.findProgramAddressSync(
+          [Buffer.from("obligation"), nftMint.toBuffer()],
+          program.programId
+        );
+        return [
+          { pubkey: nftMint, isSigner: false, isWritable: true },
+          {
+            pubkey: getAssociatedTokenAddressSync(nftMint, host.publicKey, false, TOKEN_2022_PROGRAM_ID),
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: obligation, isSigner: false, isWritable: true },
+          { pubkey: noncePageFor(nonce), isSigner: false, isWritable: true },
+          { pubkey: program.programId, isSigner: false, isWritable: false }, // No property
+        ];
+      });
+
+      return program.methods
+        .mintBookingsBatch(bookings.map(({ envelope }) => envelope))
+        .accounts({
+          host: host.publicKey,
+          integrationConfig: integrationConfigPda,
+          integrationWallet: integrationWallet.publicKey,
+          integratorBond: integratorBondPda,
+          protocolConfig: protocolConfigPda,
+          sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .remainingAccounts(remainingAccounts)
+        .preInstructions([
+          ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+          oracleSignature(batchMessage), // Must sit right before the mint
+        ])
+        .signers([host])
+        .rpc();
+    };
+
+    // The same booking twice in one batch
+    await expectRejected(mintBatch([30, 30]), "NonceAlreadyUsed");
+
+    // A batch mints once; replaying it burns nothing new
+    await mintBatch([31, 32]);
+    await expectRejected(mintBatch([31, 32]), "NonceAlreadyUsed");
+
+    // Nor can a batch re-mint a nonce claimed through a root (test 7)
+    await expectRejected(mintBatch([20]), "NonceAlreadyUsed");
+    console.log("✅ Sensor Tripped: Batch nonces are single-use.");
+  });
+
+  it("9. Safety Sensor: Settle On Behalf Needs a Funded Loan", async () => {
+    // The root-minted booking (test 7) was never used as collateral
+    const rootHostNftAta = getAssociatedTokenAddressSync(
+      rootNftMint.publicKey, host.publicKey, false, TOKEN_2022_PROGRAM_ID
+    );
+    await expectRejected(
+      program.methods
+        .settleOnBehalf(Array.from(Buffer.alloc(32, 7)), new anchor.BN(0))
+        .accounts({
+          payer: provider.wallet.publicKey,
+          obligation: rootObligationPda,
+          hostWallet: host.publicKey,
+          loanMint: usdcMint.publicKey,
+          payerTokenAta: adminUsdcAta,
+          supportedToken: supportedTokenPda,
+          tokenVault: tokenVault,
+          protocolConfig: protocolConfigPda,
+          treasuryTokenAta: treasuryUsdcAta,
+          reserveFund: reserveFundPda,
+          reserveTokenAta: reserveUsdcAta,
+          integrationConfig: integrationConfigPda,
+          hostProfile: hostProfilePda,
+          property: null,
+          nftMint: rootNftMint.publicKey,
+          vaultNftAta: rootHostNftAta, // Never locked: the NFT is still with the host
+          hostNftAta: rootHostNftAta,
+          sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc(),
+      "LoanNotFunded"
+    );
+    console.log("✅ Sensor Tripped: Nothing to settle on an unfunded booking.");
+  });
+
+  it("10. Repayment Escrow: Funded, Cranked Only When Due, Refunded Once Settled", async () => {
+    const rootHostNftAta = getAssociatedTokenAddressSync(
+      rootNftMint.publicKey, host.publicKey, false, TOKEN_2022_PROGRAM_ID
+    );
+    const rootVaultNftAta = getAssociatedTokenAddressSync(
+      rootNftMint.publicKey, rootObligationPda, true, TOKEN_2022_PROGRAM_ID
+    );
+    const escrowUsdcAta = getAssociatedTokenAddressSync(
+      usdcMint.publicKey, rootObligationPda, true, TOKEN_PROGRAM_ID
+    );
+
+    // 1. Borrow against the root-minted booking
+    await program.methods
+      .depositCollateral(new anchor.BN(500000))
+      .accounts({
+        host: host.publicKey,
+        obligation: rootObligationPda,
+        nftMint: rootNftMint.publicKey,
+        loanMint: usdcMint.publicKey,
+        hostNftAta: rootHostNftAta,
+        hostTokenAta: hostUsdcAta,
+        vaultNftAta: rootVaultNftAta,
+        poolVault: poolVaultPda,
+        tokenVault: tokenVault,
+        integrationConfig: integrationConfigPda,
+        supportedToken: supportedTokenPda,
+        hostProfile: hostProfilePda,
+        property: null,
+        protocolConfig: protocolConfigPda,
+        bookingCurrencyFeed: null,
+        bookingPriceFeed: null,
+        loanCurrencyFeed: null,
+        loanPriceFeed: null,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([host])
+      .rpc();
+
+    // 2. Pre-fund the repayment
+    const escrowed = 600000;
+    const fundEscrow = (funder: Keypair) => program.methods
+      .fundRepaymentEscrow(new anchor.BN(escrowed))
+      .accounts({
+        funder: funder.publicKey,
+        obligation: rootObligationPda,
+        integrationConfig: integrationConfigPda,
+        nftMint: rootNftMint.publicKey,
+        loanMint: usdcMint.publicKey,
+        funderTokenAta: hostUsdcAta,
+        escrowTokenAta: escrowUsdcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([funder])
+      .rpc();
+    await fundEscrow(host);
+
+    // Only the host or the vouching integrator may fund: leftovers are refunded to them
+    await expectRejected(fundEscrow(Keypair.generate()), "NotEscrowFunder");
+
+    // 3. Keepers can't crank a booking that hasn't ended
+    const crank = program.methods
+      .crankSettle()
+      .accounts({
+        keeper: provider.wallet.publicKey,
+        obligation: rootObligationPda,
+        hostWallet: host.publicKey,
+        loanMint: usdcMint.publicKey,
+        escrowTokenAta: escrowUsdcAta,
+        keeperTokenAta: adminUsdcAta,
+        hostTokenAta: hostUsdcAta,
+        integratorTokenAta: null, // The host funded the whole escrow
+        supportedToken: supportedTokenPda,
+        tokenVault: tokenVault,
+        protocolConfig: protocolConfigPda,
+        treasuryTokenAta: treasuryUsdcAta,
+        reserveFund: reserveFundPda,
+        reserveTokenAta: reserveUsdcAta,
+        integrationConfig: integrationConfigPda,
+        hostProfile: hostProfilePda,
+        property: null,
+        nftMint: rootNftMint.publicKey,
+        vaultNftAta: rootVaultNftAta,
+        hostNftAta: rootHostNftAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      });
+    await expectRejected(crank.rpc(), "BookingNotYetEnded");
+
+    // 4. ... and the host can't pull the escrow while the loan is live
+    const withdrawEscrow = () => program.methods
+      .withdrawRepaymentEscrow()
+      .accounts({
+        host: host.publicKey,
+        obligation: rootObligationPda,
+        nftMint: rootNftMint.publicKey,
+        loanMint: usdcMint.publicKey,
+        escrowTokenAta: escrowUsdcAta,
+        hostTokenAta: hostUsdcAta,
+        integrationConfig: integrationConfigPda,
+        integratorTokenAta: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([host])
+      .rpc();
+    await expectRejected(withdrawEscrow(), "EscrowStillCommitted");
+
+    // 5. The host settles directly instead; the escrow is no longer committed
+    await program.methods
+      .settleBooking()
+      .accounts({
+        host: host.publicKey,
+        obligation: rootObligationPda,
+        nftMint: rootNftMint.publicKey,
+        hostNftAta: rootHostNftAta,
+        vaultNftAta: rootVaultNftAta,
+        loanMint: usdcMint.publicKey,
+        hostTokenAta: hostUsdcAta,
+        supportedToken: supportedTokenPda,
+        tokenVault: tokenVault,
+        treasuryTokenAta: treasuryUsdcAta,
+        protocolConfig: protocolConfigPda,
+        reserveFund: reserveFundPda,
+        reserveTokenAta: reserveUsdcAta,
+        integrationConfig: integrationConfigPda,
+        hostProfile: hostProfilePda,
+        property: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([host])
+      .rpc();
+    await expectRejected(crank.rpc(), "AlreadySettled");
+
+    // 6. Nothing stranded: the escrow comes back to the host and its account closes
+    const before = await provider.connection.getTokenAccountBalance(hostUsdcAta);
+    await withdrawEscrow();
+    const after = await provider.connection.getTokenAccountBalance(hostUsdcAta);
+    expect(Number(after.value.amount) - Number(before.value.amount)).to.equal(escrowed);
+    expect(await provider.connection.getAccountInfo(escrowUsdcAta)).to.be.null;
+    console.log("✅ Escrow Returned: Settled loans never strand the repayment escrow.");
+  });
+
+  it("11. Safety Sensor: Only Defaulted Loans Can Be Written Off", async () => {
+    // Test 1's booking was repaid in test 4; it never had a liquidator
+    const noLiquidator = SystemProgram.programId;
+    await expectRejected(
+      program.methods
+        .writeOff()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          obligation: obligationPda,
+          protocolConfig: protocolConfigPda,
+          supportedToken: supportedTokenPda,
+          reserveFund: reserveFundPda,
+          integrationConfig: integrationConfigPda,
+          hostProfile: hostProfilePda,
+          property: null,
+          loanMint: usdcMint.publicKey,
+          tokenVault: tokenVault,
+          reserveTokenAta: reserveUsdcAta,
+          liquidator: noLiquidator,
+          liquidatorTokenAta: getAssociatedTokenAddressSync(
+            usdcMint.publicKey, noLiquidator, true, TOKEN_PROGRAM_ID
+          ),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc(),
+      "NotDefaulted"
+    );
+    console.log("✅ Sensor Tripped: Repaid loans stay off the loss books.");
+  });
+
+  it("12. Safety Sensor: Only Seized Bookings Can Be Redeemed", async () => {
+    const noLiquidator = SystemProgram.programId;
+    await expectRejected(
+      program.methods
+        .redeemDefaulted()
+        .accounts({
+          host: host.publicKey,
+          obligation: obligationPda,
+          nftMint: nftMint.publicKey,
+          loanMint: usdcMint.publicKey,
+          hostTokenAta: hostUsdcAta,
+          poolVault: poolVaultPda,
+          supportedToken: supportedTokenPda,
+          tokenVault: tokenVault,
+          protocolConfig: protocolConfigPda,
+          treasuryTokenAta: treasuryUsdcAta,
+          reserveFund: reserveFundPda,
+          reserveTokenAta: reserveUsdcAta,
+          integrationConfig: integrationConfigPda,
+          integratorBond: integratorBondPda,
+          bondMint: usdcMint.publicKey,
+          bondTokenAta: bondUsdcAta,
+          reserveBondAta: reserveUsdcAta,
+          liquidator: noLiquidator,
+          liquidatorTokenAta: getAssociatedTokenAddressSync(
+            usdcMint.publicKey, noLiquidator, true, TOKEN_PROGRAM_ID
+          ),
+          hostProfile: hostProfilePda,
+          property: null,
+          poolNftAta: poolNftAta,
+          hostNftAta: hostNftAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          bondTokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([host])
+        .rpc(),
+      "NotDefaulted"
+    );
+    console.log("✅ Sensor Tripped: A repaid booking has nothing to redeem.");
+  });
+});
@@ -0,0 +1,547 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { RentFlow } from "../target/types/rent_flow";
+import {
+  PublicKey, Keypair, SystemProgram, Transaction,
+  SYSVAR_INSTRUCTIONS_PUBKEY, LAMPORTS_PER_SOL, Ed25519Program
+} from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, MINT_SIZE,
+  getAssociatedTokenAddressSync, createInitializeMint2Instruction,
+  createAssociatedTokenAccountIdempotentInstruction, createMintToInstruction, unpackAccount
+} from "@solana/spl-token";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { expect } from "chai";
+import * as nacl from "tweetnacl";
+
+// Defaults only happen after end_date + grace, and every duration sits behind the
+// timelock, so these lifecycles run in-process where the clock can be moved.
+describe("rent_flow_defaults", () => {
+  const host = Keypair.generate();
+  const liquidator = Keypair.generate();
+  const integrationWallet = Keypair.generate();
+  const usdcMint = Keypair.generate();
+  const investorWallet = Keypair.generate().publicKey;
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<RentFlow>;
+  let admin: Keypair; // Protocol admin, oracle, integrator authority and LP
+
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const ata = (mint: PublicKey, owner: PublicKey, tokenProgram = TOKEN_PROGRAM_ID) =>
+    getAssociatedTokenAddressSync(mint, owner, true, tokenProgram);
+
+  let integrationConfigPda: PublicKey;
+  let integratorBondPda: PublicKey;
+  let poolVaultPda: PublicKey;
+  let protocolConfigPda: PublicKey;
+  let reserveFundPda: PublicKey;
+  let supportedTokenPda: PublicKey;
+  let hostProfilePda: PublicKey;
+  let tokenVault: PublicKey;
+  let reserveUsdcAta: PublicKey;
+  let treasuryUsdcAta: PublicKey;
+  let bondUsdcAta: PublicKey;
+  let adminUsdcAta: PublicKey;
+  let hostUsdcAta: PublicKey;
+  let liquidatorUsdcAta: PublicKey;
+
+  // 🧰 HELPERS
+  const now = async () => Number((await context.banksClient.getClock()).unixTimestamp);
+
+  // Moves to the next slot as well: a fresh blockhash keeps a retried transaction from being deduplicated
+  const warpTo = async (unixTimestamp: number) => {
+    const clock = await context.banksClient.getClock();
+    const slot = clock.slot + BigInt(1);
+    context.warpToSlot(slot);
+    context.setClock(new Clock(
+      slot, clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(unixTimestamp)
+    ));
+  };
+
+  const balance = async (address: PublicKey) => {
+    const info = await context.banksClient.getAccount(address);
+    if (info === null) return 0;
+    return Number(unpackAccount(address, { ...info, data: Buffer.from(info.data) }, info.owner).amount);
+  };
+
+  const expectRejected = async (tx: Promise<unknown>, code: string) => {
+    let thrown: any = null;
+    try {
+      await tx;
+    } catch (err: any) {
+      thrown = err;
+    }
+    expect(thrown, `Should have thrown ${code}`).to.not.be.null;
+    const logs = thrown.logs ? thrown.logs.join("\n") : "";
+    expect(thrown.toString() + logs).to.include(code);
+  };
+
+  // A booking for `host`, with its obligation and NFT accounts
+  const booking = (nftMint: Keypair) => {
+    const obligation = pda(Buffer.from("obligation"), nftMint.publicKey.toBuffer());
+    return {
+      nftMint,
+      obligation,
+      hostNftAta: ata(nftMint.publicKey, host.publicKey, TOKEN_2022_PROGRAM_ID),
+      vaultNftAta: ata(nftMint.publicKey, obligation, TOKEN_2022_PROGRAM_ID),
+      poolNftAta: ata(nftMint.publicKey, poolVaultPda, TOKEN_2022_PROGRAM_ID),
+    };
+  };
+  type Booking = ReturnType<typeof booking>;
+
+  // Mints a booking ending two minutes from now, then borrows 0.5 USDC against it
+  const openLoan = async (bookingId: string, nonce: number, b: Booking) => {
+    const t = await now();
+    const proof = {
+      bookingId,
+      amount: new anchor.BN(1000000),
+      startDate: new anchor.BN(t + 60),
+      endDate: new anchor.BN(t + 120),
+      hostWallet: host.publicKey,
+      oraclePubkey: admin.publicKey,
+      tierIndex: 0,
+      investorWallet,
+      issuedAt: new anchor.BN(t - 10),
+      expiresAt: new anchor.BN(t + 600),
+      nonce: new anchor.BN(nonce),
+    };
+    const bookingIdLen = Buffer.alloc(4);
+    bookingIdLen.writeUInt32LE(Buffer.byteLength(bookingId), 0);
+    const message = Buffer.concat([
+      Buffer.from("rent_flow:booking:v1"),
+      bookingIdLen, Buffer.from(bookingId),
+      proof.amount.toArrayLike(Buffer, "le", 8),
+      proof.startDate.toArrayLike(Buffer, "le", 8),
+      proof.endDate.toArrayLike(Buffer, "le", 8),
+      host.publicKey.toBuffer(),
+      admin.publicKey.toBuffer(),
+      Buffer.from([proof.tierIndex]),
+      investorWallet.toBuffer(),
+      proof.issuedAt.toArrayLike(Buffer, "le", 8),
+      proof.expiresAt.toArrayLike(Buffer, "le", 8),
+      proof.nonce.toArrayLike(Buffer, "le", 8),
+    ]);
+
+    await program.methods
+      .mintBooking({ version: 1, proof, extensions: Buffer.alloc(0) })
+      .accounts({
+        host: host.publicKey,
+        integrationConfig: integrationConfigPda,
+        integrationWallet: integrationWallet.publicKey,
+        integratorBond: integratorBondPda,
+        protocolConfig: protocolConfigPda,
+        nftMint: b.nftMint.publicKey,
+        hostAta: b.hostNftAta,
+        bookingObligation: b.obligation,
+        noncePage: pda(Buffer.from("nonce_page"), admin.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)),
+        property: null,
+        sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPublicKey({
+          publicKey: admin.publicKey.toBytes(),
+          message,
+          signature: nacl.sign.detached(message, admin.secretKey),
+        }),
+      ])
+      .signers([host, b.nftMint])
+      .rpc();
+
+    await program.methods
+      .depositCollateral(new anchor.BN(500000))
+      .accounts({
+        host: host.publicKey,
+        obligation: b.obligation,
+        nftMint: b.nftMint.publicKey,
+        loanMint: usdcMint.publicKey,
+        hostNftAta: b.hostNftAta,
+        hostTokenAta: hostUsdcAta,
+        vaultNftAta: b.vaultNftAta,
+        poolVault: poolVaultPda,
+        tokenVault: tokenVault,
+        integrationConfig: integrationConfigPda,
+        supportedToken: supportedTokenPda,
+        hostProfile: hostProfilePda,
+        property: null,
+        protocolConfig: protocolConfigPda,
+        bookingCurrencyFeed: null,
+        bookingPriceFeed: null,
+        loanCurrencyFeed: null,
+        loanPriceFeed: null,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([host])
+      .rpc();
+  };
+
+  const liquidate = (b: Booking) => program.methods
+    .liquidateDefault()
+    .accounts({
+      liquidator: liquidator.publicKey,
+      obligation: b.obligation,
+      nftMint: b.nftMint.publicKey,
+      vaultNftAta: b.vaultNftAta,
+      poolNftAta: b.poolNftAta,
+      poolVault: poolVaultPda,
+      protocolConfig: protocolConfigPda,
+      integrationConfig: integrationConfigPda,
+      supportedToken: supportedTokenPda,
+      integratorBond: integratorBondPda,
+      bondMint: usdcMint.publicKey,
+      bondTokenAta: bondUsdcAta,
+      reserveFund: reserveFundPda,
+      reserveBondAta: reserveUsdcAta, // The bond is in the loan's currency: slashed into the pool
+      loanMint: usdcMint.publicKey,
+      tokenVault: tokenVault,
+      liquidatorTokenAta: liquidatorUsdcAta,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      bondTokenProgram: TOKEN_PROGRAM_ID,
+      hostProfile: hostProfilePda,
+      property: null,
+      token2022Program: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    })
+    .signers([liquidator])
+    .rpc();
+
+  const cure = (b: Booking) => program.methods
+    .cureHostDefault()
+    .accounts({
+      admin: admin.publicKey,
+      protocolConfig: protocolConfigPda,
+      hostProfile: hostProfilePda,
+      obligation: b.obligation,
+    })
+    .rpc();
+
+  let bookingA: Booking; // Redeemed by the host
+  let bookingB: Booking; // Written off
+
+  before(async () => {
+    const funded = (address: PublicKey) => ({
+      address,
+      info: { lamports: 10 * LAMPORTS_PER_SOL, data: Buffer.alloc(0), owner: SystemProgram.programId, executable: false },
+    });
+    context = await startAnchor(".", [], [funded(host.publicKey), funded(liquidator.publicKey)]);
+    provider = new BankrunProvider(context);
+    program = new Program<RentFlow>(anchor.workspace.RentFlow.idl, provider);
+    admin = context.payer;
+
+    integrationConfigPda = pda(Buffer.from("integrator"), integrationWallet.publicKey.toBuffer());
+    integratorBondPda = pda(Buffer.from("integrator_bond"), integrationConfigPda.toBuffer());
+    poolVaultPda = pda(Buffer.from("pool_vault"));
+    protocolConfigPda = pda(Buffer.from("protocol_config"));
+    reserveFundPda = pda(Buffer.from("reserve_fund"));
+    supportedTokenPda = pda(Buffer.from("vault"), usdcMint.publicKey.toBuffer());
+    hostProfilePda = pda(Buffer.from("host_profile"), host.publicKey.toBuffer());
+    tokenVault = ata(usdcMint.publicKey, supportedTokenPda);
+    reserveUsdcAta = ata(usdcMint.publicKey, reserveFundPda);
+    treasuryUsdcAta = ata(usdcMint.publicKey, protocolConfigPda);
+    bondUsdcAta = ata(usdcMint.publicKey, integratorBondPda);
+    adminUsdcAta = ata(usdcMint.publicKey, admin.publicKey);
+    hostUsdcAta = ata(usdcMint.publicKey, host.publicKey);
+    liquidatorUsdcAta = ata(usdcMint.publicKey, liquidator.publicKey);
+
+    // USDC (standard Token Program): 100 to the admin, 10 to the host
+    const rent = await context.banksClient.getRent();
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: usdcMint.publicKey,
+        space: MINT_SIZE,
+        lamports: Number(rent.minimumBalance(BigInt(MINT_SIZE))),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      createInitializeMint2Instruction(usdcMint.publicKey, 6, admin.publicKey, null),
+      createAssociatedTokenAccountIdempotentInstruction(admin.publicKey, adminUsdcAta, admin.publicKey, usdcMint.publicKey),
+      createAssociatedTokenAccountIdempotentInstruction(admin.publicKey, hostUsdcAta, host.publicKey, usdcMint.publicKey),
+      createMintToInstruction(usdcMint.publicKey, adminUsdcAta, admin.publicKey, 100000000),
+      createMintToInstruction(usdcMint.publicKey, hostUsdcAta, admin.publicKey, 10000000),
+    ), [usdcMint]);
+
+    await program.methods
+      .initialize()
+      .accounts({
+        authority: admin.publicKey,
+        integrationConfig: integrationConfigPda,
+        integrationWallet: integrationWallet.publicKey,
+        poolVault: poolVaultPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeProtocol(1000)
+      .accounts({
+        admin: admin.publicKey,
+        protocolConfig: protocolConfigPda,
+        reserveFund: reserveFundPda,
+        usdcMint: usdcMint.publicKey,
+        reserveUsdcAta: reserveUsdcAta,
+        treasuryUsdcAta: treasuryUsdcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .postIntegratorBond(new anchor.BN(50000000)) // 50 USDC
+      .accounts({
+        authority: admin.publicKey,
+        integrationConfig: integrationConfigPda,
+        integrationWallet: integrationWallet.publicKey,
+        integratorBond: integratorBondPda,
+        usdcMint: usdcMint.publicKey,
+        authorityUsdcAta: adminUsdcAta,
+        bondUsdcAta: bondUsdcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setIntegratorStatus(true)
+      .accounts({
+        admin: admin.publicKey,
+        protocolConfig: protocolConfigPda,
+        integrationConfig: integrationConfigPda,
+        integrationWallet: integrationWallet.publicKey,
+        integratorBond: integratorBondPda,
+      })
+      .rpc();
+
+    await program.methods
+      .addToken(7500, Array.from(Buffer.from("USD")))
+      .accounts({
+        admin: admin.publicKey,
+        protocolConfig: protocolConfigPda,
+        mint: usdcMint.publicKey,
+        supportedToken: supportedTokenPda,
+        tokenVault: tokenVault,
+        reserveFund: reserveFundPda,
+        treasuryTokenAta: treasuryUsdcAta,
+        reserveTokenAta: reserveUsdcAta,
+        flexibleTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(new anchor.BN(20000000)) // 20 USDC
+      .accounts({
+        investor: admin.publicKey,
+        position: pda(Buffer.from("position"), admin.publicKey.toBuffer(), usdcMint.publicKey.toBuffer()),
+        supportedToken: supportedTokenPda,
+        protocolConfig: protocolConfigPda,
+        investorTokenAta: adminUsdcAta,
+        tokenVault: tokenVault,
+        mint: usdcMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    // Two loans, both left to default
+    bookingA = booking(Keypair.generate());
+    bookingB = booking(Keypair.generate());
+    await openLoan("DEFAULT-REDEEM", 1, bookingA);
+    await openLoan("DEFAULT-WRITE-OFF", 2, bookingB);
+  });
+
+  it("1. Default: Seize Both Bookings Past Their Grace Period", async () => {
+    const obligation = await program.account.bookingObligation.fetch(bookingA.obligation);
+    await expectRejected(liquidate(bookingA), "GracePeriodNotOver");
+
+    await warpTo(obligation.endDate.toNumber() + obligation.gracePeriod.toNumber() + 1);
+    const vaultBefore = await balance(tokenVault);
+    await liquidate(bookingA);
+    await liquidate(bookingB);
+
+    // Half the debt is slashed from the bond into the pool; the pool advances each bounty
+    const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+    const slash = (500000 * config.slashBps) / 10000;
+    const bounty = config.liquidationBounty.fixed.toNumber() + (500000 * config.liquidationBounty.bps) / 10000;
+    const bond = await program.account.integratorBond.fetch(integratorBondPda);
+    expect(bond.amount.toNumber()).to.equal(50000000 - 2 * slash);
+    expect(bond.totalSlashed.toNumber()).to.equal(2 * slash);
+    expect(await balance(liquidatorUsdcAta)).to.equal(2 * bounty);
+    expect(await balance(tokenVault)).to.equal(vaultBefore + 2 * slash - 2 * bounty);
+
+    const seized = await program.account.bookingObligation.fetch(bookingA.obligation);
+    expect(seized.status).to.have.property("defaulted");
+    expect(seized.bondSlashed.toNumber()).to.equal(slash);
+    expect(seized.bountyPaid.toNumber()).to.equal(bounty);
+    expect(await balance(bookingA.poolNftAta)).to.equal(1);
+
+    const pool = await program.account.supportedToken.fetch(supportedTokenPda);
+    expect(pool.outstandingPrincipal.toNumber()).to.equal(2 * (500000 + bounty));
+
+    const profile = await program.account.hostProfile.fetch(hostProfilePda);
+    expect(profile.activeDefaults).to.equal(2);
+    expect(profile.defaultCount).to.equal(2);
+    console.log("✅ Seized: Bond slashed, bounties advanced, host blocked.");
+  });
+
+  it("2. Right to Cure: The Host Redeems a Seized Booking", async () => {
+    const seized = await program.account.bookingObligation.fetch(bookingA.obligation);
+    const before = {
+      host: await balance(hostUsdcAta),
+      vault: await balance(tokenVault),
+      treasury: await balance(treasuryUsdcAta),
+      reserve: await balance(reserveUsdcAta),
+      bond: await balance(bondUsdcAta),
+    };
+
+    await program.methods
+      .redeemDefaulted()
+      .accounts({
+        host: host.publicKey,
+        obligation: bookingA.obligation,
+        nftMint: bookingA.nftMint.publicKey,
+        loanMint: usdcMint.publicKey,
+        hostTokenAta: hostUsdcAta,
+        poolVault: poolVaultPda,
+        supportedToken: supportedTokenPda,
+        tokenVault: tokenVault,
+        protocolConfig: protocolConfigPda,
+        treasuryTokenAta: treasuryUsdcAta,
+        reserveFund: reserveFundPda,
+        reserveTokenAta: reserveUsdcAta,
+        integrationConfig: integrationConfigPda,
+        integratorBond: integratorBondPda,
+        bondMint: usdcMint.publicKey,
+        bondTokenAta: bondUsdcAta,
+        reserveBondAta: reserveUsdcAta,
+        liquidator: liquidator.publicKey,
+        liquidatorTokenAta: liquidatorUsdcAta,
+        hostProfile: hostProfilePda,
+        property: null,
+        poolNftAta: bookingA.poolNftAta,
+        hostNftAta: bookingA.hostNftAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        bondTokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([host])
+      .rpc();
+
+    // The host pays principal + interest + the default penalty, plus the advanced bounty
+    const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+    const principal = 500000;
+    const interest = Math.floor((principal * seized.rateBps.toNumber()) / 10000);
+    const penalty = Math.floor((principal * config.redemption.penaltyBps) / 10000);
+    const bounty = seized.bountyPaid.toNumber();
+    const slash = seized.bondSlashed.toNumber();
+    const paid = before.host - (await balance(hostUsdcAta));
+    expect(paid).to.equal(principal + interest + penalty + bounty);
+
+    // Pool gets principal, LP yield and the bounty back, less the bond it refunds
+    const feeCut = (await balance(treasuryUsdcAta)) - before.treasury;
+    const reserveCut = (await balance(reserveUsdcAta)) - before.reserve;
+    expect(reserveCut).to.be.greaterThan(0);
+    expect((await balance(tokenVault)) - before.vault).to.equal(paid - feeCut - reserveCut - slash);
+
+    // The slash is undone
+    expect((await balance(bondUsdcAta)) - before.bond).to.equal(slash);
+    const bond = await program.account.integratorBond.fetch(integratorBondPda);
+    expect(bond.totalSlashed.toNumber()).to.equal(slash); // Only booking B's slash remains
+
+    const redeemed = await program.account.bookingObligation.fetch(bookingA.obligation);
+    expect(redeemed.status).to.have.property("repaid");
+    expect(redeemed.bondSlashed.toNumber()).to.equal(0);
+    expect(await balance(bookingA.hostNftAta)).to.equal(1);
+
+    const pool = await program.account.supportedToken.fetch(supportedTokenPda);
+    expect(pool.outstandingPrincipal.toNumber()).to.equal(500000 + bounty); // Booking B's claim
+
+    const profile = await program.account.hostProfile.fetch(hostProfilePda);
+    expect(profile.activeDefaults).to.equal(1); // Booking B's default stays open
+    console.log("✅ Redeemed: NFT back, bond refunded, one default cleared.");
+  });
+
+  it("3. Write-Off: The Reserve Covers First, LPs Absorb the Rest", async () => {
+    const seized = await program.account.bookingObligation.fetch(bookingB.obligation);
+    const writeOff = () => program.methods
+      .writeOff()
+      .accounts({
+        authority: admin.publicKey,
+        obligation: bookingB.obligation,
+        protocolConfig: protocolConfigPda,
+        supportedToken: supportedTokenPda,
+        reserveFund: reserveFundPda,
+        integrationConfig: integrationConfigPda,
+        hostProfile: hostProfilePda,
+        property: null,
+        loanMint: usdcMint.publicKey,
+        tokenVault: tokenVault,
+        reserveTokenAta: reserveUsdcAta,
+        liquidator: liquidator.publicKey,
+        liquidatorTokenAta: liquidatorUsdcAta,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await expectRejected(writeOff(), "RedemptionWindowOpen");
+
+    await warpTo(seized.redeemDeadline.toNumber() + 1);
+    const reserveBefore = await balance(reserveUsdcAta);
+    const vaultBefore = await balance(tokenVault);
+    const poolBefore = await program.account.supportedToken.fetch(supportedTokenPda);
+    await writeOff();
+
+    // Claim = debt + advanced bounty; the slashed bond already sits in the pool
+    const claim = 500000 + seized.bountyPaid.toNumber();
+    const shortfall = claim - seized.bondSlashed.toNumber();
+    const cover = Math.min(shortfall, reserveBefore);
+    expect(cover).to.be.greaterThan(0);
+    expect(await balance(reserveUsdcAta)).to.equal(reserveBefore - cover);
+    expect(await balance(tokenVault)).to.equal(vaultBefore + cover);
+
+    const reserve = await program.account.reserveFund.fetch(reserveFundPda);
+    expect(reserve.totalCovered.toNumber()).to.equal(cover);
+
+    const pool = await program.account.supportedToken.fetch(supportedTokenPda);
+    const lpLoss = shortfall - cover;
+    expect(pool.outstandingPrincipal.toNumber()).to.equal(0);
+    expect(pool.totalLosses.toNumber()).to.equal(lpLoss);
+    expect(pool.totalLiquidityTracked.toNumber()).to.equal(poolBefore.totalLiquidityTracked.toNumber() - lpLoss);
+
+    const writtenOff = await program.account.bookingObligation.fetch(bookingB.obligation);
+    expect(writtenOff.status).to.have.property("writtenOff");
+    expect(writtenOff.reserveCovered.toNumber()).to.equal(cover);
+
+    // A written-off default still blocks the host until the admin cures it
+    let profile = await program.account.hostProfile.fetch(hostProfilePda);
+    expect(profile.activeDefaults).to.equal(1);
+    expect(profile.outstandingPrincipal.toNumber()).to.equal(0);
+
+    await cure(bookingB);
+    profile = await program.account.hostProfile.fetch(hostProfilePda);
+    expect(profile.activeDefaults).to.equal(0);
+    expect((await program.account.bookingObligation.fetch(bookingB.obligation)).defaultCured).to.be.true;
+
+    // Each default is cured once, and a redeemed one has nothing left to cure
+    await warpTo(await now());
+    await expectRejected(cure(bookingB), "NoActiveDefault");
+    await expectRejected(cure(bookingA), "NoActiveDefault");
+    console.log("✅ Written Off: Reserve drained first, loss socialized, default cured once.");
+  });
+});